@@ -0,0 +1,147 @@
+use crate::compare::VersionBump;
+use crate::findings::{Finding, FindingKind};
+use crate::lockfile::normalize_name;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub package: String,
+    pub old_version: String,
+    pub new_version: String,
+}
+
+/// An accepted finding, matched by package and kind regardless of its message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BaselineFinding {
+    pub package: String,
+    pub kind: FindingKind,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub bumps: Vec<BaselineEntry>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub findings: Vec<BaselineFinding>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BaselineResult {
+    /// Bumps that are not recorded in the baseline.
+    pub bumps: Vec<VersionBump>,
+    /// Bumps that matched a baseline entry.
+    pub suppressed: Vec<VersionBump>,
+    /// Baseline entries that no longer match any bump.
+    pub stale: Vec<BaselineEntry>,
+    /// Findings that are not recorded in the baseline.
+    pub findings: Vec<Finding>,
+    /// Findings that matched a baseline entry.
+    pub suppressed_findings: Vec<Finding>,
+    /// Baseline findings that no longer match any finding.
+    pub stale_findings: Vec<BaselineFinding>,
+}
+
+impl BaselineEntry {
+    fn matches(&self, bump: &VersionBump) -> bool {
+        normalize_name(&self.package) == normalize_name(&bump.package_name)
+            && self.old_version == bump.old_version
+            && self.new_version == bump.new_version
+    }
+}
+
+impl BaselineFinding {
+    fn matches(&self, finding: &Finding) -> bool {
+        normalize_name(&self.package) == normalize_name(&finding.package_name)
+            && self.kind == finding.kind
+    }
+}
+
+impl Baseline {
+    pub fn from_bumps(bumps: &[VersionBump]) -> Self {
+        Baseline {
+            bumps: bumps
+                .iter()
+                .map(|b| BaselineEntry {
+                    package: b.package_name.clone(),
+                    old_version: b.old_version.clone(),
+                    new_version: b.new_version.clone(),
+                })
+                .collect(),
+            findings: Vec::new(),
+        }
+    }
+
+    /// Records the bumps together with each finding's package and kind.
+    pub fn from_changes(bumps: &[VersionBump], findings: &[Finding]) -> Self {
+        let mut findings: Vec<BaselineFinding> = findings
+            .iter()
+            .map(|f| BaselineFinding {
+                package: f.package_name.clone(),
+                kind: f.kind,
+            })
+            .collect();
+        findings.dedup();
+
+        Baseline {
+            findings,
+            ..Baseline::from_bumps(bumps)
+        }
+    }
+
+    /// The number of recorded bumps and findings.
+    pub fn len(&self) -> usize {
+        self.bumps.len() + self.findings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn apply(&self, bumps: Vec<VersionBump>, findings: Vec<Finding>) -> BaselineResult {
+        let (suppressed, bumps): (Vec<VersionBump>, Vec<VersionBump>) = bumps
+            .into_iter()
+            .partition(|bump| self.bumps.iter().any(|entry| entry.matches(bump)));
+
+        let stale = self
+            .bumps
+            .iter()
+            .filter(|entry| !suppressed.iter().any(|bump| entry.matches(bump)))
+            .cloned()
+            .collect();
+
+        let (suppressed_findings, findings): (Vec<Finding>, Vec<Finding>) = findings
+            .into_iter()
+            .partition(|finding| self.findings.iter().any(|entry| entry.matches(finding)));
+
+        let stale_findings = self
+            .findings
+            .iter()
+            .filter(|entry| !suppressed_findings.iter().any(|f| entry.matches(f)))
+            .cloned()
+            .collect();
+
+        BaselineResult {
+            bumps,
+            suppressed,
+            stale,
+            findings,
+            suppressed_findings,
+            stale_findings,
+        }
+    }
+}
+
+pub fn read_baseline(path: &Path) -> Result<Baseline, Box<dyn std::error::Error>> {
+    let content = fs::read_to_string(path)?;
+    let baseline: Baseline = serde_json::from_str(&content)?;
+
+    Ok(baseline)
+}
+
+pub fn write_baseline(path: &Path, baseline: &Baseline) -> Result<(), Box<dyn std::error::Error>> {
+    let content = serde_json::to_string_pretty(baseline)?;
+    fs::write(path, content + "\n")?;
+
+    Ok(())
+}
@@ -0,0 +1,215 @@
+use crate::constraint::Constraint;
+use crate::lockfile::{load_lockfile, normalize_name};
+use crate::pyproject::parse_pyproject;
+use pep440_rs::Version;
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::Path;
+
+/// `[tool.poetry]` keys hashed even when absent (as `null`) by lock files of
+/// projects without a `[project]` section.
+const LEGACY_POETRY_KEYS: &[&str] = &["dependencies", "source", "extras", "dev-dependencies"];
+const POETRY_KEYS: &[&str] = &[
+    "dependencies",
+    "source",
+    "extras",
+    "dev-dependencies",
+    "group",
+];
+const PROJECT_KEYS: &[&str] = &["requires-python", "dependencies", "optional-dependencies"];
+
+/// A dependency declared in `pyproject.toml` that the lock does not satisfy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeclarationMismatch {
+    pub package_name: String,
+    pub group: String,
+    pub constraint: String,
+    pub problem: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckResult {
+    /// The lock's `[metadata] content-hash`, if it records one.
+    pub lock_hash: Option<String>,
+    /// The content-hash computed from `pyproject.toml`.
+    pub pyproject_hash: String,
+    pub mismatches: Vec<DeclarationMismatch>,
+}
+
+impl CheckResult {
+    /// Whether the lock needs to be regenerated: its content-hash differs
+    /// from the project's or a declaration is not satisfied by it.
+    pub fn is_stale(&self) -> bool {
+        self.lock_hash
+            .as_ref()
+            .is_some_and(|hash| *hash != self.pyproject_hash)
+            || !self.mismatches.is_empty()
+    }
+}
+
+/// Compares a lock file against the `pyproject.toml` it was generated from.
+pub fn check_lock(
+    pyproject_path: &Path,
+    lock_path: &Path,
+) -> Result<CheckResult, Box<dyn std::error::Error>> {
+    let pyproject_data: toml::Value = toml::from_str(&fs::read_to_string(pyproject_path)?)?;
+    let pyproject = parse_pyproject(pyproject_path)?;
+    let lockfile = load_lockfile(lock_path)?;
+
+    let mut mismatches = Vec::new();
+    for declared in &pyproject.dependencies {
+        let problem = match lockfile.packages.get(&normalize_name(&declared.name)) {
+            None => "not in the lock".to_string(),
+            Some(locked) => {
+                let satisfied = match (
+                    declared.constraint.parse::<Constraint>(),
+                    locked.version.parse::<Version>(),
+                ) {
+                    (Ok(constraint), Ok(version)) => constraint.contains(&version),
+                    _ => true,
+                };
+                if satisfied {
+                    continue;
+                }
+                format!("locked version {} does not satisfy it", locked.version)
+            }
+        };
+        mismatches.push(DeclarationMismatch {
+            package_name: declared.name.clone(),
+            group: declared.group.clone(),
+            constraint: declared.constraint.clone(),
+            problem,
+        });
+    }
+
+    Ok(CheckResult {
+        lock_hash: lockfile.metadata.content_hash,
+        pyproject_hash: content_hash(&pyproject_data),
+        mismatches,
+    })
+}
+
+/// Computes Poetry's content-hash: the SHA-256 of the dependency-relevant
+/// parts of `pyproject.toml`, serialized like Python's
+/// `json.dumps(content, sort_keys=True)`.
+pub fn content_hash(pyproject: &toml::Value) -> String {
+    let to_json = |value: &toml::Value| serde_json::to_value(value).unwrap_or(Value::Null);
+    let project = pyproject.get("project");
+    let poetry = pyproject.get("tool").and_then(|t| t.get("poetry"));
+
+    let mut project_content = Map::new();
+    for key in PROJECT_KEYS {
+        if let Some(value) = project.and_then(|p| p.get(*key)) {
+            project_content.insert(key.to_string(), to_json(value));
+        }
+    }
+
+    // Absent legacy keys hash as `null`, unless the project uses `[project]`.
+    let mut poetry_content = Map::new();
+    for key in POETRY_KEYS {
+        match poetry.and_then(|p| p.get(*key)) {
+            Some(value) => {
+                poetry_content.insert(key.to_string(), to_json(value));
+            }
+            None if LEGACY_POETRY_KEYS.contains(key) && project_content.is_empty() => {
+                poetry_content.insert(key.to_string(), Value::Null);
+            }
+            None => {}
+        }
+    }
+
+    let content = if project_content.is_empty() {
+        // Older lock files hash the `[tool.poetry]` keys at the top level.
+        Value::Object(poetry_content)
+    } else {
+        let mut content = Map::new();
+        content.insert("project".to_string(), Value::Object(project_content));
+        content.insert(
+            "tool".to_string(),
+            serde_json::json!({ "poetry": poetry_content }),
+        );
+        if let Some(groups) = pyproject.get("dependency-groups") {
+            content.insert("dependency-groups".to_string(), to_json(groups));
+        }
+        Value::Object(content)
+    };
+
+    let mut json = String::new();
+    write_python_json(&mut json, &content);
+    format!("{:x}", Sha256::digest(json.as_bytes()))
+}
+
+/// Serializes like Python's `json.dumps(value, sort_keys=True)`: `", "` and
+/// `": "` separators and ASCII-only strings.
+fn write_python_json(out: &mut String, value: &Value) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => match n.as_f64() {
+            Some(f) if n.is_f64() => out.push_str(&python_float(f)),
+            _ => out.push_str(&n.to_string()),
+        },
+        Value::String(s) => out.push_str(&python_json_string(s)),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write_python_json(out, item);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+
+            out.push('{');
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                out.push_str(&python_json_string(key));
+                out.push_str(": ");
+                write_python_json(out, value);
+            }
+            out.push('}');
+        }
+    }
+}
+
+fn python_json_string(s: &str) -> String {
+    let mut out = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{08}' => out.push_str("\\b"),
+            '\u{0c}' => out.push_str("\\f"),
+            c if c.is_ascii() && !c.is_ascii_control() => out.push(c),
+            c => {
+                let mut units = [0u16; 2];
+                for unit in c.encode_utf16(&mut units) {
+                    out.push_str(&format!("\\u{:04x}", unit));
+                }
+            }
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Formats a float like Python's `repr`, e.g. `1.0` or `1e+20`.
+fn python_float(f: f64) -> String {
+    let repr = format!("{:?}", f);
+    match repr.split_once('e') {
+        Some((mantissa, exponent)) if !exponent.starts_with('-') => {
+            format!("{}e+{}", mantissa, exponent)
+        }
+        _ => repr,
+    }
+}
@@ -1,21 +1,141 @@
-use clap::Parser;
+use crate::markers::{Environment, Platform};
+use crate::output::OutputFormat;
+use crate::policy::FailOn;
+use crate::report::SortBy;
+use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
 #[command(name = "pdrift")]
 #[command(about = "Compare Poetry lock files and detect breaking version bumps")]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Path to the old poetry.lock file
-    pub old_lock: PathBuf,
+    #[arg(required = true)]
+    pub old_lock: Option<PathBuf>,
 
     /// Path to the new poetry.lock file
-    pub new_lock: PathBuf,
+    #[arg(required = true)]
+    pub new_lock: Option<PathBuf>,
 
-    /// Output results as JSON
-    #[arg(long)]
+    /// Output results as JSON (shorthand for --format json)
+    #[arg(long, conflicts_with = "format")]
     pub json: bool,
 
+    /// Output format
+    #[arg(long, value_enum, default_value = "text")]
+    pub format: OutputFormat,
+
     /// Include non-breaking changes in the output
     #[arg(long)]
     pub all: bool,
+
+    /// Summarize package counts and artifact sizes of both lock files
+    #[arg(long)]
+    pub footprint: bool,
+
+    /// How to order the reported changes
+    #[arg(long, value_enum, default_value = "name")]
+    pub sort: SortBy,
+
+    /// Which changes cause a non-zero exit code (comma-separated or repeated)
+    #[arg(long, value_enum, value_delimiter = ',', default_value = "breaking")]
+    pub fail_on: Vec<FailOn>,
+
+    /// Only fail on changes to direct dependencies
+    #[arg(long, requires = "pyproject")]
+    pub direct_only: bool,
+
+    /// Path to the project's pyproject.toml, used to tell direct dependencies from transitive ones
+    #[arg(long, value_name = "FILE")]
+    pub pyproject: Option<PathBuf>,
+
+    /// Path to the pyproject.toml before the change, to report constraint changes against --pyproject
+    #[arg(long, value_name = "FILE", requires = "pyproject")]
+    pub old_pyproject: Option<PathBuf>,
+
+    /// Python versions the project targets (defaults to the lock's [metadata] python-versions)
+    #[arg(long = "python-version", value_name = "VERSION", value_delimiter = ',')]
+    pub python_versions: Vec<String>,
+
+    /// Fail when a package stops shipping wheels for this tag, e.g. cp39 or manylinux2014_x86_64 (comma-separated or repeated)
+    #[arg(long = "require-wheel-tag", value_name = "TAG", value_delimiter = ',')]
+    pub required_wheel_tags: Vec<String>,
+
+    /// Only compare packages installed on this Python version, according to their markers
+    #[arg(long, value_name = "VERSION")]
+    pub target_python: Option<String>,
+
+    /// Only compare packages installed on this platform, according to their markers
+    #[arg(long, value_enum, value_name = "PLATFORM")]
+    pub target_platform: Option<Platform>,
+
+    /// Also report which changes apply in this environment, e.g. 3.12:linux (repeatable)
+    #[arg(
+        long = "env",
+        value_name = "PYTHON:PLATFORM",
+        conflicts_with_all = ["target_python", "target_platform"]
+    )]
+    pub environments: Vec<Environment>,
+
+    /// Only check packages in these dependency groups (comma-separated or repeated)
+    #[arg(long = "group", value_name = "GROUP", value_delimiter = ',')]
+    pub groups: Vec<String>,
+
+    /// Skip packages that only belong to these dependency groups
+    #[arg(long = "exclude-group", value_name = "GROUP", value_delimiter = ',')]
+    pub exclude_groups: Vec<String>,
+
+    /// Record the current version bumps and findings to a baseline file and exit
+    #[arg(long, value_name = "FILE", conflicts_with = "baseline")]
+    pub write_baseline: Option<PathBuf>,
+
+    /// Only fail on version bumps and findings not recorded in the baseline file
+    #[arg(long, value_name = "FILE")]
+    pub baseline: Option<PathBuf>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Check whether a lock file is up to date with pyproject.toml
+    Check {
+        /// Path to the project's pyproject.toml
+        #[arg(long, value_name = "FILE", default_value = "pyproject.toml")]
+        pyproject: PathBuf,
+
+        /// Path to the lock file
+        #[arg(long, value_name = "FILE", default_value = "poetry.lock")]
+        lock: PathBuf,
+
+        /// Output results as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// List declared dependencies whose constraints allow a breaking bump
+    Lint {
+        /// Path to the project's pyproject.toml
+        #[arg(long, value_name = "FILE", default_value = "pyproject.toml")]
+        pyproject: PathBuf,
+
+        /// Path to the lock file
+        #[arg(long, value_name = "FILE", default_value = "poetry.lock")]
+        lock: PathBuf,
+
+        /// Output results as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Check that every requirement recorded in a lock file is met by the lock itself
+    Validate {
+        /// Path to the lock file
+        #[arg(long, value_name = "FILE", default_value = "poetry.lock")]
+        lock: PathBuf,
+
+        /// Output results as JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
@@ -1,13 +1,33 @@
-use crate::lockfile::LockedPackage;
+use crate::constraint::Constraint;
+use crate::findings::{sort_findings, Finding, FindingKind, Severity};
+use crate::lockfile::{normalize_name, Dependency, LockMetadata, LockedPackage, PackageSource};
+use crate::pyproject::Pyproject;
 use pep440_rs::Version;
-use std::collections::HashMap;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct VersionBump {
     pub package_name: String,
     pub old_version: String,
     pub new_version: String,
     pub is_breaking: bool,
+    /// Shortest dependency chains from a root dependency to this package.
+    pub paths: Vec<Vec<String>>,
+    /// Locked packages that require this package directly.
+    pub direct_dependents: Vec<String>,
+    /// Locked packages that require this package directly or transitively.
+    pub dependents: Vec<String>,
+}
+
+/// A lock-wide `[metadata]` value that differs between the two locks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetadataChange {
+    /// The field name as written in the lock, e.g. `python-versions`.
+    pub field: &'static str,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
 }
 
 pub fn is_breaking_bump(old_version: &str, new_version: &str) -> bool {
@@ -35,6 +55,18 @@ pub fn is_breaking_bump(old_version: &str, new_version: &str) -> bool {
     false
 }
 
+/// The lowest release that would be a breaking bump from `version`: the next
+/// major release, or the next minor release for 0.x versions.
+pub fn next_breaking_version(version: &Version) -> Version {
+    let release = version.release();
+    let major = release.first().copied().unwrap_or(0);
+    if major > 0 {
+        Version::new([major + 1, 0, 0])
+    } else {
+        Version::new([0, release.get(1).copied().unwrap_or(0) + 1, 0])
+    }
+}
+
 pub fn compare_packages(
     old_packages: &HashMap<String, LockedPackage>,
     new_packages: &HashMap<String, LockedPackage>,
@@ -51,6 +83,7 @@ pub fn compare_packages(
                     old_version: old_package.version.clone(),
                     new_version: new_package.version.clone(),
                     is_breaking,
+                    ..Default::default()
                 });
             }
         }
@@ -64,3 +97,888 @@ pub fn compare_packages(
 
     bumps
 }
+
+/// Lists the lock metadata fields whose values changed, such as the tool that
+/// generated the lock or the project's Python range.
+pub fn compare_metadata(old: &LockMetadata, new: &LockMetadata) -> Vec<MetadataChange> {
+    [
+        ("generator", &old.generator, &new.generator),
+        ("lock-version", &old.lock_version, &new.lock_version),
+        (
+            "python-versions",
+            &old.python_versions,
+            &new.python_versions,
+        ),
+        ("content-hash", &old.content_hash, &new.content_hash),
+    ]
+    .into_iter()
+    .filter(|(_, old_value, new_value)| old_value != new_value)
+    .map(|(field, old_value, new_value)| MetadataChange {
+        field,
+        old_value: old_value.clone(),
+        new_value: new_value.clone(),
+    })
+    .collect()
+}
+
+/// Whether the minor version increased within the same major version. Versions
+/// that are not valid PEP 440 are never a minor bump.
+pub fn is_minor_bump(old_version: &str, new_version: &str) -> bool {
+    let (Ok(old_v), Ok(new_v)) = (
+        old_version.parse::<Version>(),
+        new_version.parse::<Version>(),
+    ) else {
+        return false;
+    };
+
+    let old_release = old_v.release();
+    let new_release = new_v.release();
+
+    old_release.first().unwrap_or(&0) == new_release.first().unwrap_or(&0)
+        && old_release.get(1).unwrap_or(&0) < new_release.get(1).unwrap_or(&0)
+}
+
+pub fn added_packages(
+    old_packages: &HashMap<String, LockedPackage>,
+    new_packages: &HashMap<String, LockedPackage>,
+) -> Vec<LockedPackage> {
+    missing_from(new_packages, old_packages)
+}
+
+pub fn removed_packages(
+    old_packages: &HashMap<String, LockedPackage>,
+    new_packages: &HashMap<String, LockedPackage>,
+) -> Vec<LockedPackage> {
+    missing_from(old_packages, new_packages)
+}
+
+fn missing_from(
+    packages: &HashMap<String, LockedPackage>,
+    other: &HashMap<String, LockedPackage>,
+) -> Vec<LockedPackage> {
+    let mut missing: Vec<LockedPackage> = packages
+        .iter()
+        .filter(|(package_name, _)| !other.contains_key(*package_name))
+        .map(|(_, package)| package.clone())
+        .collect();
+
+    missing.sort_by_key(|p| p.name.to_lowercase());
+
+    missing
+}
+
+/// Packages present in both lock files, as `(old, new)` pairs.
+fn shared_packages<'a>(
+    old_packages: &'a HashMap<String, LockedPackage>,
+    new_packages: &'a HashMap<String, LockedPackage>,
+) -> Vec<(&'a LockedPackage, &'a LockedPackage)> {
+    old_packages
+        .iter()
+        .filter_map(|(package_name, old_package)| {
+            new_packages
+                .get(package_name)
+                .map(|new_package| (old_package, new_package))
+        })
+        .collect()
+}
+
+/// Compares each shared package's own `[package.dependencies]`, reporting
+/// requirements that were added, removed, or changed constraint, markers,
+/// extras or optional flag.
+pub fn compare_dependencies(
+    old_packages: &HashMap<String, LockedPackage>,
+    new_packages: &HashMap<String, LockedPackage>,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for (old_package, new_package) in shared_packages(old_packages, new_packages) {
+        let old_requirements = requirements_by_name(&old_package.dependencies);
+        let new_requirements = requirements_by_name(&new_package.dependencies);
+
+        for (dependency_name, old_specs) in &old_requirements {
+            match new_requirements.get(dependency_name) {
+                None => findings.push(Finding {
+                    package_name: new_package.name.clone(),
+                    kind: FindingKind::DependencyRemoved,
+                    severity: Severity::Low,
+                    message: format!("no longer requires {}", old_specs.join(" | ")),
+                }),
+                Some(new_specs) if new_specs != old_specs => findings.push(Finding {
+                    package_name: new_package.name.clone(),
+                    kind: FindingKind::DependencyChanged,
+                    severity: Severity::Low,
+                    message: format!(
+                        "requirement changed: {} → {}",
+                        old_specs.join(" | "),
+                        new_specs.join(" | ")
+                    ),
+                }),
+                Some(_) => {}
+            }
+        }
+
+        for (dependency_name, new_specs) in &new_requirements {
+            if !old_requirements.contains_key(dependency_name) {
+                findings.push(Finding {
+                    package_name: new_package.name.clone(),
+                    kind: FindingKind::DependencyAdded,
+                    severity: Severity::Low,
+                    message: format!("now requires {}", new_specs.join(" | ")),
+                });
+            }
+        }
+    }
+
+    sort_findings(&mut findings);
+
+    findings
+}
+
+/// Groups requirements by normalized dependency name. A dependency may be
+/// listed several times with different markers, so each name maps to the
+/// sorted descriptions of all its entries.
+fn requirements_by_name(dependencies: &[Dependency]) -> BTreeMap<String, Vec<String>> {
+    let mut requirements: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+    for dependency in dependencies {
+        requirements
+            .entry(normalize_name(&dependency.name))
+            .or_default()
+            .push(dependency.to_string());
+    }
+    for specs in requirements.values_mut() {
+        specs.sort();
+    }
+
+    requirements
+}
+
+/// Reports packages whose dependency group membership changed. Moving into
+/// `main` means the package now ships to production and is medium severity.
+/// Nothing is reported when either lock records no groups at all.
+pub fn compare_groups(
+    old_packages: &HashMap<String, LockedPackage>,
+    new_packages: &HashMap<String, LockedPackage>,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    let records_groups =
+        |packages: &HashMap<String, LockedPackage>| packages.values().any(|p| !p.groups.is_empty());
+    if !records_groups(old_packages) || !records_groups(new_packages) {
+        return findings;
+    }
+
+    for (old_package, new_package) in shared_packages(old_packages, new_packages) {
+        let old_groups: BTreeSet<&str> = old_package.groups.iter().map(|g| g.as_str()).collect();
+        let new_groups: BTreeSet<&str> = new_package.groups.iter().map(|g| g.as_str()).collect();
+        if old_groups == new_groups {
+            continue;
+        }
+
+        let severity = if new_groups.contains("main") && !old_groups.contains("main") {
+            Severity::Medium
+        } else {
+            Severity::Low
+        };
+        let describe =
+            |groups: &BTreeSet<&str>| groups.iter().copied().collect::<Vec<_>>().join(", ");
+
+        findings.push(Finding {
+            package_name: new_package.name.clone(),
+            kind: FindingKind::GroupsChanged,
+            severity,
+            message: format!(
+                "groups changed: {} → {}",
+                describe(&old_groups),
+                describe(&new_groups)
+            ),
+        });
+    }
+
+    sort_findings(&mut findings);
+
+    findings
+}
+
+/// Reports packages that switched between optional (installed only through an
+/// extra) and required. Becoming required means the package is now always
+/// installed.
+pub fn compare_optional(
+    old_packages: &HashMap<String, LockedPackage>,
+    new_packages: &HashMap<String, LockedPackage>,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for (old_package, new_package) in shared_packages(old_packages, new_packages) {
+        let (severity, message) = match (old_package.optional, new_package.optional) {
+            (true, false) => (Severity::Medium, "became required (optional → required)"),
+            (false, true) => (Severity::Low, "became optional (required → optional)"),
+            _ => continue,
+        };
+
+        findings.push(Finding {
+            package_name: new_package.name.clone(),
+            kind: FindingKind::OptionalChanged,
+            severity,
+            message: message.to_string(),
+        });
+    }
+
+    sort_findings(&mut findings);
+
+    findings
+}
+
+/// Reports changes to the extras a package defines. Removed or redefined
+/// extras change what `pip install pkg[extra]` installs and are medium
+/// severity; new extras are low.
+pub fn compare_extras(
+    old_packages: &HashMap<String, LockedPackage>,
+    new_packages: &HashMap<String, LockedPackage>,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for (old_package, new_package) in shared_packages(old_packages, new_packages) {
+        let old_extras = extras_by_name(old_package);
+        let new_extras = extras_by_name(new_package);
+
+        for (extra, old_requirements) in &old_extras {
+            let (severity, message) = match new_extras.get(extra) {
+                None => (
+                    Severity::Medium,
+                    format!(
+                        "extra '{}' removed: {}",
+                        extra,
+                        describe_extra(old_requirements)
+                    ),
+                ),
+                Some(new_requirements) if new_requirements != old_requirements => (
+                    Severity::Medium,
+                    format!(
+                        "extra '{}' changed: {} → {}",
+                        extra,
+                        describe_extra(old_requirements),
+                        describe_extra(new_requirements)
+                    ),
+                ),
+                Some(_) => continue,
+            };
+            findings.push(Finding {
+                package_name: new_package.name.clone(),
+                kind: FindingKind::ExtrasChanged,
+                severity,
+                message,
+            });
+        }
+
+        for (extra, new_requirements) in &new_extras {
+            if !old_extras.contains_key(extra) {
+                findings.push(Finding {
+                    package_name: new_package.name.clone(),
+                    kind: FindingKind::ExtrasChanged,
+                    severity: Severity::Low,
+                    message: format!(
+                        "extra '{}' added: {}",
+                        extra,
+                        describe_extra(new_requirements)
+                    ),
+                });
+            }
+        }
+    }
+
+    sort_findings(&mut findings);
+
+    findings
+}
+
+/// A package's extras with their requirements sorted.
+fn extras_by_name(package: &LockedPackage) -> BTreeMap<String, Vec<String>> {
+    package
+        .extras
+        .iter()
+        .map(|(extra, requirements)| {
+            let mut requirements = requirements.clone();
+            requirements.sort();
+            (extra.clone(), requirements)
+        })
+        .collect()
+}
+
+fn describe_extra(requirements: &[String]) -> String {
+    if requirements.is_empty() {
+        "(none)".to_string()
+    } else {
+        requirements.join(", ")
+    }
+}
+
+/// Reports packages whose source moved, e.g. from PyPI to a private index,
+/// git repository or local path, and git packages that now resolve to a
+/// different commit.
+pub fn compare_sources(
+    old_packages: &HashMap<String, LockedPackage>,
+    new_packages: &HashMap<String, LockedPackage>,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for (old_package, new_package) in shared_packages(old_packages, new_packages) {
+        let (old_source, new_source) = (&old_package.source, &new_package.source);
+        if old_source == new_source {
+            continue;
+        }
+
+        let location = |source: &Option<PackageSource>| {
+            source
+                .as_ref()
+                .map(|s| (s.source_type.clone(), s.url.clone()))
+        };
+        let severity = if location(old_source) != location(new_source) {
+            Severity::High
+        } else {
+            Severity::Medium
+        };
+
+        findings.push(Finding {
+            package_name: new_package.name.clone(),
+            kind: FindingKind::SourceChanged,
+            severity,
+            message: format!(
+                "source changed: {} → {}",
+                describe_source(old_source),
+                describe_source(new_source)
+            ),
+        });
+    }
+
+    sort_findings(&mut findings);
+
+    findings
+}
+
+fn describe_source(source: &Option<PackageSource>) -> String {
+    match source {
+        Some(source) => source.to_string(),
+        None => "PyPI".to_string(),
+    }
+}
+
+/// Reports packages whose version is unchanged but whose artifact files or
+/// hashes differ, which can indicate a re-uploaded or tampered release.
+/// Packages without recorded files in either lock are skipped.
+pub fn compare_hashes(
+    old_packages: &HashMap<String, LockedPackage>,
+    new_packages: &HashMap<String, LockedPackage>,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for (old_package, new_package) in shared_packages(old_packages, new_packages) {
+        if old_package.version != new_package.version
+            || old_package.files.is_empty()
+            || new_package.files.is_empty()
+        {
+            continue;
+        }
+
+        let old_files = artifact_hashes(old_package);
+        let new_files = artifact_hashes(new_package);
+        if old_files == new_files {
+            continue;
+        }
+
+        let mut changes = Vec::new();
+        for (file, old_hashes) in &old_files {
+            match new_files.get(file) {
+                Some(new_hashes) if new_hashes != old_hashes => {
+                    changes.push(format!("hash changed for {}", file))
+                }
+                Some(_) => {}
+                None => changes.push(format!("removed {}", file)),
+            }
+        }
+        for file in new_files.keys() {
+            if !old_files.contains_key(file) {
+                changes.push(format!("added {}", file));
+            }
+        }
+
+        findings.push(Finding {
+            package_name: new_package.name.clone(),
+            kind: FindingKind::HashChanged,
+            severity: Severity::High,
+            message: format!(
+                "artifacts changed for unchanged version {}: {}",
+                new_package.version,
+                changes.join("; ")
+            ),
+        });
+    }
+
+    sort_findings(&mut findings);
+
+    findings
+}
+
+fn artifact_hashes(package: &LockedPackage) -> BTreeMap<&str, BTreeSet<&str>> {
+    let mut hashes: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    for artifact in &package.files {
+        hashes
+            .entry(artifact.file.as_str())
+            .or_default()
+            .insert(artifact.hash.as_str());
+    }
+    hashes
+}
+
+/// The newest Python 3 minor release. Open-ended ranges such as `>=3.9` are
+/// expanded up to it; constraints that name a newer release extend it.
+const LATEST_PYTHON_3_MINOR: u64 = 15;
+
+/// The Python minor releases allowed by a project's `python-versions` range.
+///
+/// Candidates run from 2.7 (or the lowest release the range names) through
+/// the last minor release of each major version: 2.7, 3.15, or whatever newer
+/// release the range names.
+pub fn python_targets(project_range: &str) -> Vec<Version> {
+    let Ok(constraint) = project_range.parse::<Constraint>() else {
+        return Vec::new();
+    };
+
+    let bounds: Vec<(u64, u64)> = constraint
+        .alternatives()
+        .iter()
+        .flat_map(|specifiers| specifiers.iter())
+        .map(|specifier| {
+            let release = specifier.version().release();
+            (release[0], release.get(1).copied().unwrap_or(0))
+        })
+        .collect();
+    let first = bounds.iter().copied().fold((2, 7), Ord::min);
+    let last_major = bounds.iter().map(|b| b.0).fold(3, Ord::max);
+
+    let mut targets = Vec::new();
+    for major in first.0..=last_major {
+        let known_last_minor = match major {
+            2 => Some(7),
+            3 => Some(LATEST_PYTHON_3_MINOR),
+            _ => None,
+        };
+        let named_last_minor = bounds.iter().filter(|b| b.0 == major).map(|b| b.1).max();
+        let Some(last_minor) = known_last_minor.max(named_last_minor) else {
+            continue;
+        };
+        let first_minor = if major == first.0 { first.1 } else { 0 };
+
+        targets.extend(
+            (first_minor..=last_minor)
+                .map(|minor| Version::new([major, minor]))
+                .filter(|release| constraint.contains(release)),
+        );
+    }
+
+    targets
+}
+
+/// Reports packages whose new `python-versions` no longer includes a target
+/// Python version that the old one supported.
+pub fn compare_python_support(
+    old_packages: &HashMap<String, LockedPackage>,
+    new_packages: &HashMap<String, LockedPackage>,
+    targets: &[Version],
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for (old_package, new_package) in shared_packages(old_packages, new_packages) {
+        let (Some(old_range), Some(new_range)) =
+            (&old_package.python_versions, &new_package.python_versions)
+        else {
+            continue;
+        };
+        let (Ok(old_constraint), Ok(new_constraint)) = (
+            old_range.parse::<Constraint>(),
+            new_range.parse::<Constraint>(),
+        ) else {
+            continue;
+        };
+
+        let dropped: Vec<String> = targets
+            .iter()
+            .filter(|t| old_constraint.contains(t) && !new_constraint.contains(t))
+            .map(|t| t.to_string())
+            .collect();
+        if dropped.is_empty() {
+            continue;
+        }
+
+        findings.push(Finding {
+            package_name: new_package.name.clone(),
+            kind: FindingKind::PythonSupportDropped,
+            severity: Severity::High,
+            message: format!(
+                "no longer supports Python {} (python-versions {} → {})",
+                dropped.join(", "),
+                old_range,
+                new_range
+            ),
+        });
+    }
+
+    sort_findings(&mut findings);
+
+    findings
+}
+
+/// Reports packages that shipped wheels in the old lock but only source
+/// distributions in the new one, which need a compiler toolchain to install.
+pub fn compare_sdist_only(
+    old_packages: &HashMap<String, LockedPackage>,
+    new_packages: &HashMap<String, LockedPackage>,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for (old_package, new_package) in shared_packages(old_packages, new_packages) {
+        let old_wheels = old_package
+            .files
+            .iter()
+            .filter(|a| a.wheel_tags().is_some())
+            .count();
+        if old_wheels == 0
+            || new_package.files.is_empty()
+            || new_package.files.iter().any(|a| a.wheel_tags().is_some())
+        {
+            continue;
+        }
+
+        let sdists: Vec<&str> = new_package.files.iter().map(|a| a.file.as_str()).collect();
+        findings.push(Finding {
+            package_name: new_package.name.clone(),
+            kind: FindingKind::SdistOnly,
+            severity: Severity::High,
+            message: format!(
+                "{} ships no wheels, only {} ({} had {} wheel(s))",
+                new_package.version,
+                sdists.join(", "),
+                old_package.version,
+                old_wheels
+            ),
+        });
+    }
+
+    sort_findings(&mut findings);
+
+    findings
+}
+
+/// Python, ABI and platform tags across all of a package's wheels.
+fn wheel_tag_sets(package: &LockedPackage) -> [(&'static str, BTreeSet<String>); 3] {
+    let mut sets: [(&'static str, BTreeSet<String>); 3] = [
+        ("python", BTreeSet::new()),
+        ("abi", BTreeSet::new()),
+        ("platform", BTreeSet::new()),
+    ];
+    for tags in package.files.iter().filter_map(|a| a.wheel_tags()) {
+        sets[0].1.extend(tags.python);
+        sets[1].1.extend(tags.abi);
+        sets[2].1.extend(tags.platform);
+    }
+    sets
+}
+
+/// Reports packages whose wheels cover different Python, ABI or platform
+/// tags than before. Dropped tags are medium severity, additions low.
+/// Packages without files in either lock, or without wheels in the new one,
+/// are skipped.
+pub fn compare_wheel_tags(
+    old_packages: &HashMap<String, LockedPackage>,
+    new_packages: &HashMap<String, LockedPackage>,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for (old_package, new_package) in shared_packages(old_packages, new_packages) {
+        if old_package.files.is_empty()
+            || !new_package.files.iter().any(|a| a.wheel_tags().is_some())
+        {
+            continue;
+        }
+
+        let mut dropped = false;
+        let mut changes = Vec::new();
+        for ((kind, old_tags), (_, new_tags)) in wheel_tag_sets(old_package)
+            .iter()
+            .zip(wheel_tag_sets(new_package).iter())
+        {
+            let removed: Vec<&str> = old_tags.difference(new_tags).map(|t| t.as_str()).collect();
+            let added: Vec<&str> = new_tags.difference(old_tags).map(|t| t.as_str()).collect();
+            if !removed.is_empty() {
+                dropped = true;
+                changes.push(format!("dropped {} {}", kind, removed.join(", ")));
+            }
+            if !added.is_empty() {
+                changes.push(format!("added {} {}", kind, added.join(", ")));
+            }
+        }
+        if changes.is_empty() {
+            continue;
+        }
+
+        findings.push(Finding {
+            package_name: new_package.name.clone(),
+            kind: FindingKind::WheelTagsChanged,
+            severity: if dropped {
+                Severity::Medium
+            } else {
+                Severity::Low
+            },
+            message: format!("wheel tags changed: {}", changes.join("; ")),
+        });
+    }
+
+    sort_findings(&mut findings);
+
+    findings
+}
+
+/// Whether any wheel of `package` can be installed for `tag`: a wheel covers
+/// a tag equal to one of its Python, ABI or platform tags, or to its full
+/// `python-abi-platform` triple. Pure-Python wheels (platform `any`) cover
+/// every tag.
+fn covers_wheel_tag(package: &LockedPackage, tag: &str) -> bool {
+    package
+        .files
+        .iter()
+        .filter_map(|a| a.wheel_tags())
+        .any(|tags| {
+            tags.platform.iter().any(|p| p == "any")
+                || tags.python.iter().any(|t| t == tag)
+                || tags.abi.iter().any(|t| t == tag)
+                || tags.platform.iter().any(|t| t == tag)
+                || tags.python.iter().any(|python| {
+                    tags.abi.iter().any(|abi| {
+                        tags.platform
+                            .iter()
+                            .any(|platform| format!("{}-{}-{}", python, abi, platform) == tag)
+                    })
+                })
+        })
+}
+
+/// Reports packages that shipped a wheel covering one of the `required`
+/// tags in the old lock but no longer do in the new one. Packages the new
+/// lock lists no artifacts for, such as git or path sources, are skipped.
+pub fn compare_wheel_coverage(
+    old_packages: &HashMap<String, LockedPackage>,
+    new_packages: &HashMap<String, LockedPackage>,
+    required: &[String],
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for (old_package, new_package) in shared_packages(old_packages, new_packages) {
+        if new_package.files.is_empty() {
+            continue;
+        }
+
+        let lost: Vec<&str> = required
+            .iter()
+            .filter(|tag| covers_wheel_tag(old_package, tag) && !covers_wheel_tag(new_package, tag))
+            .map(|tag| tag.as_str())
+            .collect();
+        if lost.is_empty() {
+            continue;
+        }
+
+        findings.push(Finding {
+            package_name: new_package.name.clone(),
+            kind: FindingKind::WheelCoverageLost,
+            severity: Severity::High,
+            message: format!(
+                "no longer ships a wheel for {} ({} → {})",
+                lost.join(", "),
+                old_package.version,
+                new_package.version
+            ),
+        });
+    }
+
+    sort_findings(&mut findings);
+
+    findings
+}
+
+/// How a declared dependency's constraint changed between two pyproject files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConstraintChangeKind {
+    Added,
+    Removed,
+    /// The new constraint allows every version the old one did, and more.
+    Widened,
+    /// The new constraint allows a subset of the versions the old one did.
+    Narrowed,
+    /// Each constraint allows versions the other does not.
+    Changed,
+}
+
+impl fmt::Display for ConstraintChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            ConstraintChangeKind::Added => "added",
+            ConstraintChangeKind::Removed => "removed",
+            ConstraintChangeKind::Widened => "widened",
+            ConstraintChangeKind::Narrowed => "narrowed",
+            ConstraintChangeKind::Changed => "changed",
+        };
+        f.write_str(label)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConstraintChange {
+    pub package_name: String,
+    pub group: String,
+    pub old_constraint: Option<String>,
+    pub new_constraint: Option<String>,
+    pub kind: ConstraintChangeKind,
+}
+
+/// Compares the dependencies declared in two pyproject files, per dependency
+/// and group. Rewrites that allow the same versions, such as `^2.0` to
+/// `>=2.0,<3`, are not reported.
+pub fn compare_constraints(old: &Pyproject, new: &Pyproject) -> Vec<ConstraintChange> {
+    let declarations = |pyproject: &Pyproject| -> BTreeMap<(String, String), (String, String)> {
+        pyproject
+            .dependencies
+            .iter()
+            .map(|d| {
+                (
+                    (normalize_name(&d.name), d.group.clone()),
+                    (d.name.clone(), d.constraint.clone()),
+                )
+            })
+            .collect()
+    };
+    let old_declarations = declarations(old);
+    let new_declarations = declarations(new);
+
+    let keys: BTreeSet<&(String, String)> = old_declarations
+        .keys()
+        .chain(new_declarations.keys())
+        .collect();
+
+    let mut changes = Vec::new();
+    for key in keys {
+        let old_declaration = old_declarations.get(key);
+        let new_declaration = new_declarations.get(key);
+        let kind = match (old_declaration, new_declaration) {
+            (None, Some(_)) => ConstraintChangeKind::Added,
+            (Some(_), None) => ConstraintChangeKind::Removed,
+            (Some((_, old_constraint)), Some((_, new_constraint))) => {
+                if old_constraint == new_constraint {
+                    continue;
+                }
+                match classify_constraint_change(old_constraint, new_constraint) {
+                    Some(kind) => kind,
+                    None => continue,
+                }
+            }
+            (None, None) => continue,
+        };
+
+        let (name, _) = new_declaration.or(old_declaration).unwrap();
+        changes.push(ConstraintChange {
+            package_name: name.clone(),
+            group: key.1.clone(),
+            old_constraint: old_declaration.map(|(_, c)| c.clone()),
+            new_constraint: new_declaration.map(|(_, c)| c.clone()),
+            kind,
+        });
+    }
+
+    changes.sort_by(|a, b| {
+        a.package_name
+            .to_lowercase()
+            .cmp(&b.package_name.to_lowercase())
+            .then(a.group.cmp(&b.group))
+    });
+
+    changes
+}
+
+/// Compares which versions two constraints allow by probing the versions
+/// they mention, the breaking releases after those, and the extremes.
+/// Returns `None` when the constraints allow the same probes, and `Changed`
+/// when either cannot be parsed.
+fn classify_constraint_change(old: &str, new: &str) -> Option<ConstraintChangeKind> {
+    let (Ok(old_constraint), Ok(new_constraint)) =
+        (old.parse::<Constraint>(), new.parse::<Constraint>())
+    else {
+        return Some(ConstraintChangeKind::Changed);
+    };
+
+    let mut probes: BTreeSet<Version> = BTreeSet::from([Version::new([0]), Version::new([9999])]);
+    for constraint in [&old_constraint, &new_constraint] {
+        for specifiers in constraint.alternatives() {
+            for specifier in specifiers.iter() {
+                let version = specifier.version().clone();
+                probes.insert(next_breaking_version(&version));
+                probes.insert(version);
+            }
+        }
+    }
+
+    let only_old = probes
+        .iter()
+        .any(|v| old_constraint.contains(v) && !new_constraint.contains(v));
+    let only_new = probes
+        .iter()
+        .any(|v| new_constraint.contains(v) && !old_constraint.contains(v));
+
+    match (only_old, only_new) {
+        (false, false) => None,
+        (false, true) => Some(ConstraintChangeKind::Widened),
+        (true, false) => Some(ConstraintChangeKind::Narrowed),
+        (true, true) => Some(ConstraintChangeKind::Changed),
+    }
+}
+
+/// Flags breaking bumps of declared dependencies whose constraint did not
+/// change, so the resolver picked up a new major release on its own.
+pub fn unexpected_breaking_bumps(
+    bumps: &[VersionBump],
+    constraint_changes: &[ConstraintChange],
+    pyproject: &Pyproject,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for bump in bumps.iter().filter(|b| b.is_breaking) {
+        let name = normalize_name(&bump.package_name);
+        let declared: Vec<String> = pyproject
+            .dependencies
+            .iter()
+            .filter(|d| normalize_name(&d.name) == name)
+            .map(|d| format!("{} ({})", d.constraint, d.group))
+            .collect();
+        if declared.is_empty()
+            || constraint_changes
+                .iter()
+                .any(|c| normalize_name(&c.package_name) == name)
+        {
+            continue;
+        }
+
+        findings.push(Finding {
+            package_name: bump.package_name.clone(),
+            kind: FindingKind::UnexpectedBreakingBump,
+            severity: Severity::Medium,
+            message: format!(
+                "breaking bump {} → {} without a constraint change (declared {})",
+                bump.old_version,
+                bump.new_version,
+                declared.join(", ")
+            ),
+        });
+    }
+
+    sort_findings(&mut findings);
+
+    findings
+}
@@ -0,0 +1,95 @@
+use pep440_rs::{Version, VersionSpecifiers};
+use regex::Regex;
+use std::str::FromStr;
+
+/// A version constraint in Poetry syntax: PEP 440 specifiers extended with
+/// caret (`^1.2`), tilde (`~1.2`), bare versions, wildcards and `||` unions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Constraint {
+    alternatives: Vec<VersionSpecifiers>,
+}
+
+impl Constraint {
+    /// Whether any alternative of the constraint allows `version`.
+    pub fn contains(&self, version: &Version) -> bool {
+        self.alternatives
+            .iter()
+            .any(|specifiers| specifiers.contains(version))
+    }
+
+    /// The `||`-separated alternatives, each translated to PEP 440.
+    pub fn alternatives(&self) -> &[VersionSpecifiers] {
+        &self.alternatives
+    }
+}
+
+impl FromStr for Constraint {
+    type Err = String;
+
+    fn from_str(constraint: &str) -> Result<Self, Self::Err> {
+        let term =
+            Regex::new(r"(\^|~=|~|===|==|!=|>=|<=|>|<|=)?\s*(\*|[0-9][0-9A-Za-z.*+!_-]*)").unwrap();
+
+        let mut alternatives = Vec::new();
+        for alternative in constraint.split("||") {
+            let mut specifiers = Vec::new();
+            for captures in term.captures_iter(alternative) {
+                let operator = captures.get(1).map_or("", |m| m.as_str());
+                specifiers.extend(translate(operator, &captures[2])?);
+            }
+            let specifiers = VersionSpecifiers::from_str(&specifiers.join(","))
+                .map_err(|e| format!("Invalid constraint '{}': {}", constraint, e))?;
+            alternatives.push(specifiers);
+        }
+
+        Ok(Constraint { alternatives })
+    }
+}
+
+/// Translates a single Poetry constraint term to PEP 440 specifiers.
+fn translate(operator: &str, version: &str) -> Result<Vec<String>, String> {
+    if version == "*" {
+        return Ok(Vec::new());
+    }
+
+    match operator {
+        "^" => {
+            let release = parse_release(version)?;
+            // Bump the first non-zero component, or the last one if all are zero.
+            let index = release
+                .iter()
+                .position(|&n| n != 0)
+                .unwrap_or(release.len() - 1);
+            Ok(vec![
+                format!(">={}", version),
+                format!("<{}", bump_release(&release, index)),
+            ])
+        }
+        "~" => {
+            let release = parse_release(version)?;
+            let index = if release.len() > 1 { 1 } else { 0 };
+            Ok(vec![
+                format!(">={}", version),
+                format!("<{}", bump_release(&release, index)),
+            ])
+        }
+        "" | "=" => Ok(vec![format!("=={}", version)]),
+        _ => Ok(vec![format!("{}{}", operator, version)]),
+    }
+}
+
+fn parse_release(version: &str) -> Result<Vec<u64>, String> {
+    let parsed = Version::from_str(version).map_err(|e| e.to_string())?;
+    Ok(parsed.release().to_vec())
+}
+
+/// Increments `release[index]` and drops every later component.
+fn bump_release(release: &[u64], index: usize) -> String {
+    let mut bumped: Vec<u64> = release[..=index].to_vec();
+    bumped[index] += 1;
+    bumped
+        .iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>()
+        .join(".")
+}
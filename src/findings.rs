@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Low,
+    Medium,
+    High,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Severity::Low => "low",
+            Severity::Medium => "medium",
+            Severity::High => "high",
+        };
+        f.write_str(label)
+    }
+}
+
+/// The kind of change a [`Finding`] reports, beyond plain version bumps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FindingKind {
+    DependencyAdded,
+    DependencyRemoved,
+    DependencyChanged,
+    SourceChanged,
+    HashChanged,
+    PythonSupportDropped,
+    OptionalChanged,
+    ExtrasChanged,
+    GroupsChanged,
+    WheelTagsChanged,
+    WheelCoverageLost,
+    SdistOnly,
+    UnexpectedBreakingBump,
+}
+
+impl FindingKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FindingKind::DependencyAdded => "dependency-added",
+            FindingKind::DependencyRemoved => "dependency-removed",
+            FindingKind::DependencyChanged => "dependency-changed",
+            FindingKind::SourceChanged => "source-changed",
+            FindingKind::HashChanged => "hash-changed",
+            FindingKind::PythonSupportDropped => "python-support-dropped",
+            FindingKind::OptionalChanged => "optional-changed",
+            FindingKind::ExtrasChanged => "extras-changed",
+            FindingKind::GroupsChanged => "groups-changed",
+            FindingKind::WheelTagsChanged => "wheel-tags-changed",
+            FindingKind::WheelCoverageLost => "wheel-coverage-lost",
+            FindingKind::SdistOnly => "sdist-only",
+            FindingKind::UnexpectedBreakingBump => "unexpected-breaking-bump",
+        }
+    }
+
+    /// A one-line description of the kind, e.g. for SARIF rules.
+    pub fn description(&self) -> &'static str {
+        match self {
+            FindingKind::DependencyAdded => "A package gained a requirement",
+            FindingKind::DependencyRemoved => "A package dropped a requirement",
+            FindingKind::DependencyChanged => "A package changed a requirement",
+            FindingKind::SourceChanged => "A package moved to a different source",
+            FindingKind::HashChanged => "A package's artifacts changed without a version change",
+            FindingKind::PythonSupportDropped => {
+                "A package dropped support for a targeted Python version"
+            }
+            FindingKind::OptionalChanged => "A package switched between optional and required",
+            FindingKind::ExtrasChanged => "A package's extras changed",
+            FindingKind::GroupsChanged => "A package's dependency groups changed",
+            FindingKind::WheelTagsChanged => "A package's wheel tags changed",
+            FindingKind::WheelCoverageLost => {
+                "A package no longer ships a wheel for a required tag"
+            }
+            FindingKind::SdistOnly => "A package now only ships a source distribution",
+            FindingKind::UnexpectedBreakingBump => {
+                "A declared dependency got a breaking bump without a constraint change"
+            }
+        }
+    }
+}
+
+impl fmt::Display for FindingKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub package_name: String,
+    pub kind: FindingKind,
+    pub severity: Severity,
+    pub message: String,
+}
+
+pub fn sort_findings(findings: &mut [Finding]) {
+    findings.sort_by(|a, b| {
+        a.package_name
+            .to_lowercase()
+            .cmp(&b.package_name.to_lowercase())
+            .then(a.kind.cmp(&b.kind))
+            .then(a.message.cmp(&b.message))
+    });
+}
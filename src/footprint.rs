@@ -0,0 +1,111 @@
+use crate::lockfile::LockedPackage;
+use std::collections::{BTreeSet, HashMap};
+
+/// A package whose total artifact size differs between the two locks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SizeChange {
+    pub package_name: String,
+    /// `None` when the package is missing from the lock or has no sizes.
+    pub old_size: Option<u64>,
+    pub new_size: Option<u64>,
+}
+
+impl SizeChange {
+    pub fn delta(&self) -> i64 {
+        self.new_size.unwrap_or(0) as i64 - self.old_size.unwrap_or(0) as i64
+    }
+}
+
+/// Package, artifact and size totals of the old and new locks.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Footprint {
+    pub old_packages: usize,
+    pub new_packages: usize,
+    pub old_artifacts: usize,
+    pub new_artifacts: usize,
+    /// Sum of the recorded artifact sizes, `None` when the lock has none.
+    pub old_size: Option<u64>,
+    pub new_size: Option<u64>,
+    /// Per-package size changes, largest absolute change first.
+    pub size_changes: Vec<SizeChange>,
+}
+
+impl Footprint {
+    pub fn new(
+        old_packages: &HashMap<String, LockedPackage>,
+        new_packages: &HashMap<String, LockedPackage>,
+    ) -> Self {
+        let names: BTreeSet<&String> = old_packages.keys().chain(new_packages.keys()).collect();
+
+        let mut size_changes: Vec<SizeChange> = names
+            .into_iter()
+            .filter_map(|name| {
+                let old_package = old_packages.get(name);
+                let new_package = new_packages.get(name);
+                let old_size = old_package.and_then(package_size);
+                let new_size = new_package.and_then(package_size);
+                if old_size == new_size {
+                    return None;
+                }
+                Some(SizeChange {
+                    package_name: new_package.or(old_package)?.name.clone(),
+                    old_size,
+                    new_size,
+                })
+            })
+            .collect();
+        size_changes.sort_by(|a, b| {
+            b.delta().abs().cmp(&a.delta().abs()).then_with(|| {
+                a.package_name
+                    .to_lowercase()
+                    .cmp(&b.package_name.to_lowercase())
+            })
+        });
+
+        Footprint {
+            old_packages: old_packages.len(),
+            new_packages: new_packages.len(),
+            old_artifacts: artifact_count(old_packages),
+            new_artifacts: artifact_count(new_packages),
+            old_size: total_size(old_packages),
+            new_size: total_size(new_packages),
+            size_changes,
+        }
+    }
+}
+
+/// The summed size of a package's artifacts, if any of them records one.
+fn package_size(package: &LockedPackage) -> Option<u64> {
+    let sizes: Vec<u64> = package.files.iter().filter_map(|a| a.size).collect();
+    (!sizes.is_empty()).then(|| sizes.iter().sum())
+}
+
+fn artifact_count(packages: &HashMap<String, LockedPackage>) -> usize {
+    packages.values().map(|p| p.files.len()).sum()
+}
+
+fn total_size(packages: &HashMap<String, LockedPackage>) -> Option<u64> {
+    let sizes: Vec<u64> = packages.values().filter_map(package_size).collect();
+    (!sizes.is_empty()).then(|| sizes.iter().sum())
+}
+
+/// Formats a byte count with binary units, e.g. `1.5 MiB`.
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+/// Formats a signed byte delta, e.g. `+1.5 MiB` or `-200 B`.
+pub fn format_size_delta(delta: i64) -> String {
+    let sign = if delta < 0 { "-" } else { "+" };
+    format!("{}{}", sign, format_size(delta.unsigned_abs()))
+}
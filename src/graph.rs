@@ -0,0 +1,303 @@
+use crate::lockfile::{normalize_name, LockedPackage};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+/// Upper bound on the number of shortest paths reported per package.
+pub const MAX_PATHS: usize = 3;
+
+/// Dependency edges between the packages of a single lock file, keyed by
+/// normalized package name. Requirements on packages missing from the lock
+/// are ignored.
+#[derive(Debug, Clone, Default)]
+pub struct DependencyGraph {
+    names: HashMap<String, String>,
+    dependencies: HashMap<String, BTreeSet<String>>,
+    dependents: HashMap<String, BTreeSet<String>>,
+}
+
+impl DependencyGraph {
+    pub fn new(packages: &HashMap<String, LockedPackage>) -> Self {
+        let mut graph = DependencyGraph::default();
+
+        for (package_name, package) in packages {
+            graph
+                .names
+                .insert(package_name.clone(), package.name.clone());
+            graph.dependencies.entry(package_name.clone()).or_default();
+            graph.dependents.entry(package_name.clone()).or_default();
+        }
+
+        for (package_name, package) in packages {
+            for dependency in &package.dependencies {
+                let dependency_name = normalize_name(&dependency.name);
+                if !packages.contains_key(&dependency_name) || dependency_name == *package_name {
+                    continue;
+                }
+                graph
+                    .dependencies
+                    .entry(package_name.clone())
+                    .or_default()
+                    .insert(dependency_name.clone());
+                graph
+                    .dependents
+                    .entry(dependency_name)
+                    .or_default()
+                    .insert(package_name.clone());
+            }
+        }
+
+        graph
+    }
+
+    /// The display name of a package, falling back to the normalized name.
+    pub fn display_name(&self, package_name: &str) -> String {
+        self.names
+            .get(package_name)
+            .cloned()
+            .unwrap_or_else(|| package_name.to_string())
+    }
+
+    /// Packages that no other locked package depends on.
+    pub fn roots(&self) -> HashSet<String> {
+        self.dependents
+            .iter()
+            .filter(|(_, dependents)| dependents.is_empty())
+            .map(|(package_name, _)| package_name.clone())
+            .collect()
+    }
+
+    /// Display names of the packages that require `package_name` directly.
+    pub fn direct_dependents(&self, package_name: &str) -> Vec<String> {
+        let mut dependents: Vec<String> = self
+            .dependents
+            .get(&normalize_name(package_name))
+            .into_iter()
+            .flatten()
+            .map(|dependent| self.display_name(dependent))
+            .collect();
+        dependents.sort_by_key(|name| name.to_lowercase());
+
+        dependents
+    }
+
+    /// Display names of every package that requires `package_name`, directly
+    /// or through other packages.
+    pub fn all_dependents(&self, package_name: &str) -> Vec<String> {
+        let target = normalize_name(package_name);
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut queue = VecDeque::from([target.clone()]);
+
+        while let Some(current) = queue.pop_front() {
+            for dependent in self.dependents.get(&current).into_iter().flatten() {
+                if *dependent != target && seen.insert(dependent.clone()) {
+                    queue.push_back(dependent.clone());
+                }
+            }
+        }
+
+        let mut dependents: Vec<String> = seen.iter().map(|d| self.display_name(d)).collect();
+        dependents.sort_by_key(|name| name.to_lowercase());
+
+        dependents
+    }
+
+    /// Returns up to [`MAX_PATHS`] shortest chains from any of `roots` to
+    /// `package_name`, each ordered from the root to the package.
+    pub fn shortest_paths(&self, package_name: &str, roots: &HashSet<String>) -> Vec<Vec<String>> {
+        let target = normalize_name(package_name);
+        if !self.names.contains_key(&target) {
+            return Vec::new();
+        }
+
+        // Walk up the reverse edges level by level, remembering for every
+        // node which nodes one step closer to the target discovered it.
+        let mut distance: HashMap<String, usize> = HashMap::from([(target.clone(), 0)]);
+        let mut next_hops: HashMap<String, Vec<String>> = HashMap::new();
+        let mut queue = VecDeque::from([target.clone()]);
+        let mut found_at: Option<usize> = None;
+
+        while let Some(current) = queue.pop_front() {
+            let current_distance = distance[&current];
+            if found_at.is_some_and(|d| current_distance >= d) {
+                continue;
+            }
+            if roots.contains(&current) {
+                found_at = Some(current_distance);
+                continue;
+            }
+
+            for dependent in self.dependents.get(&current).into_iter().flatten() {
+                match distance.get(dependent) {
+                    None => {
+                        distance.insert(dependent.clone(), current_distance + 1);
+                        next_hops.insert(dependent.clone(), vec![current.clone()]);
+                        queue.push_back(dependent.clone());
+                    }
+                    Some(&d) if d == current_distance + 1 => {
+                        next_hops.get_mut(dependent).unwrap().push(current.clone());
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+
+        let Some(length) = found_at else {
+            return Vec::new();
+        };
+
+        let mut starts: Vec<&String> = distance
+            .iter()
+            .filter(|(name, &d)| d == length && roots.contains(*name))
+            .map(|(name, _)| name)
+            .collect();
+        starts.sort();
+
+        let mut paths = Vec::new();
+        for start in starts {
+            self.collect_paths(start, &target, &next_hops, &mut vec![], &mut paths);
+            if paths.len() >= MAX_PATHS {
+                break;
+            }
+        }
+        paths.truncate(MAX_PATHS);
+
+        paths
+    }
+
+    /// Returns one dependency cycle per group of packages that require each
+    /// other, as display names starting and ending with the alphabetically
+    /// first package of the group, e.g. `[a, b, a]`.
+    pub fn cycles(&self) -> Vec<Vec<String>> {
+        let mut search = ComponentSearch {
+            graph: self,
+            index: HashMap::new(),
+            low_link: HashMap::new(),
+            stack: Vec::new(),
+            on_stack: HashSet::new(),
+            components: Vec::new(),
+        };
+        let mut names: Vec<&String> = self.dependencies.keys().collect();
+        names.sort();
+        for name in names {
+            if !search.index.contains_key(name) {
+                search.visit(name);
+            }
+        }
+
+        let mut cycles: Vec<Vec<String>> = search
+            .components
+            .into_iter()
+            .filter(|component| component.len() > 1)
+            .map(|component| {
+                self.cycle_within(&component)
+                    .iter()
+                    .map(|name| self.display_name(name))
+                    .collect()
+            })
+            .collect();
+        cycles.sort_by_key(|cycle| cycle[0].to_lowercase());
+
+        cycles
+    }
+
+    /// The shortest cycle through the first package of a strongly connected
+    /// component, staying inside the component.
+    fn cycle_within(&self, component: &BTreeSet<String>) -> Vec<String> {
+        let start = component.first().unwrap();
+        let mut previous: HashMap<&String, &String> = HashMap::new();
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(current) = queue.pop_front() {
+            for dependency in self.dependencies.get(current).into_iter().flatten() {
+                if !component.contains(dependency) {
+                    continue;
+                }
+                if dependency == start {
+                    let mut cycle = vec![start.clone(), current.clone()];
+                    let mut node = current;
+                    while let Some(&prior) = previous.get(node) {
+                        cycle.push(prior.clone());
+                        node = prior;
+                    }
+                    cycle.reverse();
+                    return cycle;
+                }
+                if !previous.contains_key(dependency) {
+                    previous.insert(dependency, current);
+                    queue.push_back(dependency);
+                }
+            }
+        }
+
+        vec![start.clone()]
+    }
+
+    fn collect_paths(
+        &self,
+        current: &str,
+        target: &str,
+        next_hops: &HashMap<String, Vec<String>>,
+        prefix: &mut Vec<String>,
+        paths: &mut Vec<Vec<String>>,
+    ) {
+        if paths.len() >= MAX_PATHS {
+            return;
+        }
+
+        prefix.push(self.display_name(current));
+        if current == target {
+            paths.push(prefix.clone());
+        } else {
+            let mut hops = next_hops.get(current).cloned().unwrap_or_default();
+            hops.sort();
+            for hop in &hops {
+                self.collect_paths(hop, target, next_hops, prefix, paths);
+            }
+        }
+        prefix.pop();
+    }
+}
+
+/// Tarjan's strongly connected components search over a [`DependencyGraph`].
+struct ComponentSearch<'a> {
+    graph: &'a DependencyGraph,
+    index: HashMap<String, usize>,
+    low_link: HashMap<String, usize>,
+    stack: Vec<String>,
+    on_stack: HashSet<String>,
+    components: Vec<BTreeSet<String>>,
+}
+
+impl ComponentSearch<'_> {
+    fn visit(&mut self, name: &str) {
+        let index = self.index.len();
+        self.index.insert(name.to_string(), index);
+        self.low_link.insert(name.to_string(), index);
+        self.stack.push(name.to_string());
+        self.on_stack.insert(name.to_string());
+
+        let graph = self.graph;
+        for dependency in graph.dependencies.get(name).into_iter().flatten() {
+            if !self.index.contains_key(dependency) {
+                self.visit(dependency);
+                let low = self.low_link[name].min(self.low_link[dependency]);
+                self.low_link.insert(name.to_string(), low);
+            } else if self.on_stack.contains(dependency) {
+                let low = self.low_link[name].min(self.index[dependency]);
+                self.low_link.insert(name.to_string(), low);
+            }
+        }
+
+        if self.low_link[name] == self.index[name] {
+            let mut component = BTreeSet::new();
+            while let Some(member) = self.stack.pop() {
+                self.on_stack.remove(&member);
+                let done = member == name;
+                component.insert(member);
+                if done {
+                    break;
+                }
+            }
+            self.components.push(component);
+        }
+    }
+}
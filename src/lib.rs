@@ -1,4 +1,16 @@
+pub mod baseline;
+pub mod check;
 pub mod cli;
 pub mod compare;
+pub mod constraint;
+pub mod findings;
+pub mod footprint;
+pub mod graph;
+pub mod lint;
 pub mod lockfile;
+pub mod markers;
 pub mod output;
+pub mod policy;
+pub mod pyproject;
+pub mod report;
+pub mod validate;
@@ -0,0 +1,73 @@
+use crate::compare::next_breaking_version;
+use crate::constraint::Constraint;
+use crate::lockfile::{normalize_name, LockedPackage};
+use crate::pyproject::Pyproject;
+use pep440_rs::Version;
+use std::collections::HashMap;
+
+/// A declared dependency whose constraint would let the next lock pick up a
+/// breaking release.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintIssue {
+    pub package_name: String,
+    pub group: String,
+    pub constraint: String,
+    pub locked_version: String,
+    /// The first breaking release the constraint allows.
+    pub allowed_version: String,
+    /// A caret constraint pinned to the locked version's compatible range.
+    pub suggestion: String,
+    /// The suggestion as a PEP 440 range, for `[project]` dependencies.
+    pub pep440_suggestion: String,
+}
+
+/// Pairs declared dependencies with their locked versions and reports the
+/// ones whose constraint allows the next breaking release. Dependencies
+/// missing from the lock, installed from git, a path or a URL, or with
+/// unparseable constraints are skipped.
+pub fn lint_constraints(
+    pyproject: &Pyproject,
+    packages: &HashMap<String, LockedPackage>,
+) -> Vec<LintIssue> {
+    let mut issues = Vec::new();
+
+    for declared in pyproject
+        .dependencies
+        .iter()
+        .filter(|d| !d.direct_reference)
+    {
+        let Some(locked) = packages.get(&normalize_name(&declared.name)) else {
+            continue;
+        };
+        let (Ok(constraint), Ok(version)) = (
+            declared.constraint.parse::<Constraint>(),
+            locked.version.parse::<Version>(),
+        ) else {
+            continue;
+        };
+
+        let next_breaking = next_breaking_version(&version);
+        if !constraint.contains(&next_breaking) {
+            continue;
+        }
+
+        issues.push(LintIssue {
+            package_name: declared.name.clone(),
+            group: declared.group.clone(),
+            constraint: declared.constraint.clone(),
+            locked_version: locked.version.clone(),
+            allowed_version: next_breaking.to_string(),
+            suggestion: format!("^{}", locked.version),
+            pep440_suggestion: format!(">={},<{}", locked.version, next_breaking),
+        });
+    }
+
+    issues.sort_by(|a, b| {
+        a.package_name
+            .to_lowercase()
+            .cmp(&b.package_name.to_lowercase())
+            .then(a.group.cmp(&b.group))
+    });
+
+    issues
+}
@@ -1,12 +1,154 @@
 use regex::Regex;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
 use std::fs;
 use std::path::Path;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct LockedPackage {
     pub name: String,
     pub version: String,
+    /// Dependency groups the package belongs to, e.g. `main` or `dev`. Empty
+    /// when the lock records none, as in Poetry 1.5–1.8 and uv lock files.
+    pub groups: Vec<String>,
+    /// Requirements from the package's `[package.dependencies]` table.
+    pub dependencies: Vec<Dependency>,
+    /// Where the package comes from, `None` for the default PyPI index.
+    pub source: Option<PackageSource>,
+    /// Distribution files (wheels and sdists) and their hashes.
+    pub files: Vec<Artifact>,
+    /// The package's `python-versions` constraint, e.g. `>=3.8`.
+    pub python_versions: Option<String>,
+    /// PEP 508 markers limiting where the package is installed.
+    pub markers: Option<String>,
+    /// Whether the package is only installed through an extra.
+    pub optional: bool,
+    /// The package's `[package.extras]`: extra name to the requirements it adds.
+    pub extras: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Artifact {
+    pub file: String,
+    /// The hash including its algorithm prefix, e.g. `sha256:...`.
+    pub hash: String,
+    /// The file size in bytes, recorded by uv.lock and pylock.toml.
+    pub size: Option<u64>,
+}
+
+/// The compatibility tags encoded in a wheel filename, with compressed tag
+/// sets such as `py2.py3` expanded.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WheelTags {
+    pub python: Vec<String>,
+    pub abi: Vec<String>,
+    pub platform: Vec<String>,
+}
+
+impl Artifact {
+    /// Parses the tags of a wheel named
+    /// `{name}-{version}(-{build})?-{python}-{abi}-{platform}.whl`, or `None`
+    /// for sdists and malformed names.
+    pub fn wheel_tags(&self) -> Option<WheelTags> {
+        let stem = self.file.strip_suffix(".whl")?;
+        let parts: Vec<&str> = stem.split('-').collect();
+        if parts.len() < 5 {
+            return None;
+        }
+        let expand = |tags: &str| tags.split('.').map(|t| t.to_string()).collect();
+        let [python, abi, platform] = parts[parts.len() - 3..] else {
+            return None;
+        };
+
+        Some(WheelTags {
+            python: expand(python),
+            abi: expand(abi),
+            platform: expand(platform),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PackageSource {
+    /// The source type, e.g. `git`, `legacy`, `directory`, `file` or `url`.
+    pub source_type: String,
+    pub url: String,
+    /// Branch, tag or revision requested for git sources; source name for
+    /// package indexes.
+    pub reference: Option<String>,
+    /// The commit a git reference resolved to.
+    pub resolved_reference: Option<String>,
+}
+
+/// Lock-wide information from the `[metadata]` table and header comment.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LockMetadata {
+    /// The tool that wrote the lock, e.g. `Poetry 1.8.3`.
+    pub generator: Option<String>,
+    /// The lock file format version, e.g. `2.0`.
+    pub lock_version: Option<String>,
+    /// The project's supported Python range, e.g. `^3.10`.
+    pub python_versions: Option<String>,
+    /// Poetry's hash of the relevant `pyproject.toml` sections.
+    pub content_hash: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Lockfile {
+    pub packages: HashMap<String, LockedPackage>,
+    pub metadata: LockMetadata,
+    /// 1-based line of each package's `name` key, keyed by normalized name.
+    pub package_lines: HashMap<String, usize>,
+}
+
+/// Where a lock file declares its packages, for pointing at them in reports.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LockLocation {
+    /// The lock file's path as given on the command line.
+    pub path: String,
+    pub package_lines: HashMap<String, usize>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Dependency {
+    pub name: String,
+    /// The version constraint, `*` when none is given.
+    pub constraint: String,
+    /// PEP 508 environment markers restricting when the requirement applies.
+    pub markers: Option<String>,
+    pub optional: bool,
+    pub extras: Vec<String>,
+}
+
+impl fmt::Display for PackageSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.source_type, self.url)?;
+        match (&self.reference, &self.resolved_reference) {
+            (Some(reference), Some(resolved)) if reference != resolved => {
+                write!(f, " ({} @ {})", reference, resolved)
+            }
+            (_, Some(resolved)) => write!(f, " ({})", resolved),
+            (Some(reference), None) => write!(f, " ({})", reference),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for Dependency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if !self.extras.is_empty() {
+            write!(f, "[{}]", self.extras.join(","))?;
+        }
+        write!(f, " {}", self.constraint)?;
+        if let Some(markers) = &self.markers {
+            write!(f, "; {}", markers)?;
+        }
+        if self.optional {
+            write!(f, " (optional)")?;
+        }
+        Ok(())
+    }
 }
 
 pub fn normalize_name(name: &str) -> String {
@@ -17,12 +159,19 @@ pub fn normalize_name(name: &str) -> String {
 pub fn parse_lockfile(
     path: &Path,
 ) -> Result<HashMap<String, LockedPackage>, Box<dyn std::error::Error>> {
+    Ok(load_lockfile(path)?.packages)
+}
+
+/// Parses a lock file's packages together with its `[metadata]` table.
+pub fn load_lockfile(path: &Path) -> Result<Lockfile, Box<dyn std::error::Error>> {
     let content = fs::read_to_string(path)?;
     let data: toml::Value = toml::from_str(&content)?;
 
     let mut packages = HashMap::new();
 
-    if let Some(package_array) = data.get("package").and_then(|v| v.as_array()) {
+    // pylock.toml names the array `packages`.
+    let package_array = data.get("package").or_else(|| data.get("packages"));
+    if let Some(package_array) = package_array.and_then(|v| v.as_array()) {
         for package_entry in package_array {
             let name = package_entry
                 .get("name")
@@ -36,16 +185,338 @@ pub fn parse_lockfile(
                 .ok_or("Missing 'version' field")?
                 .to_string();
 
+            let groups = parse_groups(package_entry);
+            let dependencies = parse_dependencies(package_entry);
+            let source = parse_source(package_entry);
+            let mut files = package_entry
+                .get("files")
+                .map(parse_files)
+                .unwrap_or_default();
+            // uv.lock and pylock.toml list the sdist and wheels separately.
+            files.extend(package_entry.get("sdist").and_then(parse_artifact));
+            files.extend(
+                package_entry
+                    .get("wheels")
+                    .map(parse_files)
+                    .unwrap_or_default(),
+            );
+            let python_versions = package_entry
+                .get("python-versions")
+                .and_then(|v| v.as_str())
+                .map(|v| v.to_string());
+            let markers = package_entry.get("markers").and_then(parse_package_markers);
+            let optional = package_entry
+                .get("optional")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let extras = parse_extras(package_entry);
+
             let normalized_name = normalize_name(&name);
             packages.insert(
                 normalized_name,
                 LockedPackage {
                     name: name.clone(),
                     version,
+                    groups,
+                    dependencies,
+                    source,
+                    files,
+                    python_versions,
+                    markers,
+                    optional,
+                    extras,
                 },
             );
         }
     }
 
-    Ok(packages)
+    // Poetry 1.x lock files keep artifacts in `[metadata.files]`, keyed by
+    // package name, instead of on each package.
+    if let Some(metadata_files) = data
+        .get("metadata")
+        .and_then(|m| m.get("files"))
+        .and_then(|v| v.as_table())
+    {
+        for (name, files) in metadata_files {
+            if let Some(package) = packages.get_mut(&normalize_name(name)) {
+                if package.files.is_empty() {
+                    package.files = parse_files(files);
+                }
+            }
+        }
+    }
+
+    let metadata_field = |key: &str| {
+        data.get("metadata")
+            .and_then(|m| m.get(key))
+            .and_then(|v| v.as_str())
+            .map(|v| v.to_string())
+    };
+    // uv.lock and pylock.toml keep the equivalents at the top level.
+    let top_level_field = |key: &str| match data.get(key)? {
+        toml::Value::String(value) => Some(value.clone()),
+        toml::Value::Integer(value) => Some(value.to_string()),
+        _ => None,
+    };
+    let metadata = LockMetadata {
+        generator: parse_generator(&content),
+        lock_version: metadata_field("lock-version")
+            .or_else(|| top_level_field("lock-version"))
+            .or_else(|| top_level_field("version")),
+        python_versions: metadata_field("python-versions")
+            .or_else(|| top_level_field("requires-python")),
+        content_hash: metadata_field("content-hash"),
+    };
+
+    Ok(Lockfile {
+        packages,
+        metadata,
+        package_lines: package_lines(&content),
+    })
+}
+
+/// Finds the `name = "..."` line opening each `[[package]]` (or pylock.toml
+/// `[[packages]]`) entry. Later entries of a repeated name win, as in
+/// [`load_lockfile`].
+fn package_lines(content: &str) -> HashMap<String, usize> {
+    let name_pattern = Regex::new(r#"^name\s*=\s*["']([^"']+)["']"#).unwrap();
+    let mut lines = HashMap::new();
+    let mut in_package = false;
+
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_package = line == "[[package]]" || line == "[[packages]]";
+        } else if in_package {
+            if let Some(captures) = name_pattern.captures(line) {
+                lines.insert(normalize_name(&captures[1]), index + 1);
+                in_package = false;
+            }
+        }
+    }
+
+    lines
+}
+
+/// Reads the generating tool from a leading comment such as
+/// `# This file is automatically @generated by Poetry 1.8.3 and should not be
+/// changed by hand.`
+fn parse_generator(content: &str) -> Option<String> {
+    let re = Regex::new(r"generated by (.+?)(?: and should not be changed by hand)?\.?$").unwrap();
+    content
+        .lines()
+        .take_while(|line| line.starts_with('#'))
+        .find_map(|line| re.captures(line.trim()))
+        .map(|captures| captures[1].to_string())
+}
+
+/// Reads group membership from Poetry 2.x `groups` arrays, falling back to the
+/// Poetry 1.x `category` field. Packages without either have no groups.
+fn parse_groups(package_entry: &toml::Value) -> Vec<String> {
+    if let Some(groups) = package_entry.get("groups").and_then(|v| v.as_array()) {
+        return groups
+            .iter()
+            .filter_map(|g| g.as_str())
+            .map(|g| g.to_string())
+            .collect();
+    }
+
+    if let Some(category) = package_entry.get("category").and_then(|v| v.as_str()) {
+        return vec![category.to_string()];
+    }
+
+    Vec::new()
+}
+
+/// Reads a package's `markers`, either a single expression or, in newer lock
+/// files, a table of expressions per dependency group that are combined.
+fn parse_package_markers(markers: &toml::Value) -> Option<String> {
+    match markers {
+        toml::Value::String(markers) => Some(markers.clone()),
+        toml::Value::Table(by_group) => {
+            let mut alternatives: Vec<&str> =
+                by_group.values().filter_map(|v| v.as_str()).collect();
+            alternatives.sort();
+            alternatives.dedup();
+            match alternatives.as_slice() {
+                [] => None,
+                [single] => Some(single.to_string()),
+                _ => Some(
+                    alternatives
+                        .iter()
+                        .map(|m| format!("({})", m))
+                        .collect::<Vec<_>>()
+                        .join(" or "),
+                ),
+            }
+        }
+        _ => None,
+    }
+}
+
+fn parse_extras(package_entry: &toml::Value) -> BTreeMap<String, Vec<String>> {
+    let Some(table) = package_entry.get("extras").and_then(|v| v.as_table()) else {
+        return BTreeMap::new();
+    };
+
+    table
+        .iter()
+        .map(|(extra, requirements)| {
+            let requirements = requirements
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|r| r.as_str())
+                .map(|r| r.to_string())
+                .collect();
+            (extra.clone(), requirements)
+        })
+        .collect()
+}
+
+fn parse_files(files: &toml::Value) -> Vec<Artifact> {
+    files
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(parse_artifact)
+        .collect()
+}
+
+/// Reads a Poetry `{file, hash}` entry, a uv `{url, hash, size}` entry or a
+/// pylock `{name, hashes, size}` entry.
+fn parse_artifact(entry: &toml::Value) -> Option<Artifact> {
+    let field = |key: &str| entry.get(key).and_then(|v| v.as_str());
+
+    let file = field("file")
+        .or_else(|| field("name"))
+        .or_else(|| field("filename"))
+        .or_else(|| field("url").and_then(|url| url.rsplit('/').next()))
+        .or_else(|| field("path").and_then(|path| path.rsplit('/').next()))?
+        .to_string();
+
+    let hash = match field("hash") {
+        Some(hash) => hash.to_string(),
+        None => entry
+            .get("hashes")
+            .and_then(|v| v.as_table())
+            .and_then(|hashes| hashes.iter().next())
+            .and_then(|(algorithm, digest)| Some(format!("{}:{}", algorithm, digest.as_str()?)))
+            .unwrap_or_default(),
+    };
+
+    Some(Artifact {
+        file,
+        hash,
+        size: entry
+            .get("size")
+            .and_then(|v| v.as_integer())
+            .and_then(|size| u64::try_from(size).ok()),
+    })
+}
+
+/// Reads `[package.source]`. Packages from the default PyPI index have none.
+fn parse_source(package_entry: &toml::Value) -> Option<PackageSource> {
+    let source = package_entry.get("source")?;
+    let field = |key: &str| {
+        source
+            .get(key)
+            .and_then(|v| v.as_str())
+            .filter(|v| !v.is_empty())
+            .map(|v| v.to_string())
+    };
+
+    Some(PackageSource {
+        source_type: field("type").unwrap_or_default(),
+        url: field("url").unwrap_or_default(),
+        reference: field("reference"),
+        resolved_reference: field("resolved_reference"),
+    })
+}
+
+/// Reads `[package.dependencies]`, where each requirement is a constraint
+/// string, a table, or an array of tables with per-marker constraints.
+fn parse_dependencies(package_entry: &toml::Value) -> Vec<Dependency> {
+    let mut dependencies = Vec::new();
+
+    let Some(table) = package_entry.get("dependencies").and_then(|v| v.as_table()) else {
+        return dependencies;
+    };
+
+    for (name, specification) in table {
+        match specification {
+            toml::Value::Array(alternatives) => {
+                for alternative in alternatives {
+                    dependencies.push(parse_dependency(name, alternative));
+                }
+            }
+            _ => dependencies.push(parse_dependency(name, specification)),
+        }
+    }
+
+    dependencies
+}
+
+fn parse_dependency(name: &str, specification: &toml::Value) -> Dependency {
+    let field = |key: &str| specification.get(key).and_then(|v| v.as_str());
+
+    let constraint = match specification {
+        toml::Value::String(constraint) => constraint.clone(),
+        _ => field("version").unwrap_or("*").to_string(),
+    };
+
+    let extras = specification
+        .get("extras")
+        .and_then(|v| v.as_array())
+        .map(|extras| {
+            extras
+                .iter()
+                .filter_map(|e| e.as_str())
+                .map(|e| e.to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Dependency {
+        name: name.to_string(),
+        constraint,
+        markers: field("markers").map(|m| m.to_string()),
+        optional: specification
+            .get("optional")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+        extras,
+    }
+}
+
+/// Keeps packages that belong to at least one of `include` (or any group when
+/// `include` is empty) and drops packages whose groups are all in `exclude`;
+/// packages without groups are never excluded. Fails when filters are given
+/// but the lock records no groups at all.
+pub fn filter_by_groups(
+    packages: HashMap<String, LockedPackage>,
+    include: &[String],
+    exclude: &[String],
+) -> Result<HashMap<String, LockedPackage>, Box<dyn std::error::Error>> {
+    if include.is_empty() && exclude.is_empty() {
+        return Ok(packages);
+    }
+    if !packages.is_empty() && packages.values().all(|p| p.groups.is_empty()) {
+        return Err(
+            "the lock file records no dependency groups, so --group and \
+             --exclude-group cannot be applied (lock files from Poetry 1.5 to 1.8 \
+             and uv omit them)"
+                .into(),
+        );
+    }
+
+    Ok(packages
+        .into_iter()
+        .filter(|(_, package)| {
+            include.is_empty() || package.groups.iter().any(|g| include.contains(g))
+        })
+        .filter(|(_, package)| {
+            package.groups.is_empty() || !package.groups.iter().all(|g| exclude.contains(g))
+        })
+        .collect())
 }
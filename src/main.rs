@@ -1,41 +1,295 @@
 use clap::Parser;
-use pdrift_rs::cli::Cli;
-use pdrift_rs::compare::compare_packages;
-use pdrift_rs::lockfile::parse_lockfile;
-use pdrift_rs::output::{format_json, format_text};
+use pdrift_rs::baseline::{read_baseline, write_baseline, Baseline};
+use pdrift_rs::check::check_lock;
+use pdrift_rs::cli::{Cli, Command};
+use pdrift_rs::compare::{
+    compare_constraints, compare_metadata, compare_python_support, compare_wheel_coverage,
+    python_targets, unexpected_breaking_bumps,
+};
+use pdrift_rs::footprint::Footprint;
+use pdrift_rs::lint::lint_constraints;
+use pdrift_rs::lockfile::{filter_by_groups, load_lockfile, parse_lockfile, LockLocation};
+use pdrift_rs::markers::{filter_by_environment, Environment};
+use pdrift_rs::output::{
+    format_check_json, format_check_text, format_lint_json, format_lint_text, format_report,
+    format_validate_json, format_validate_text, OutputFormat,
+};
+use pdrift_rs::policy::{evaluate, Policy, EXIT_ERROR, EXIT_OK, EXIT_POLICY_VIOLATION};
+use pdrift_rs::pyproject::{parse_pyproject, Pyproject};
+use pdrift_rs::report::Report;
+use pdrift_rs::validate::validate_lock;
+use std::path::Path;
 use std::process;
 
 fn main() {
     let args = Cli::parse();
 
-    let old_packages = match parse_lockfile(&args.old_lock) {
-        Ok(packages) => packages,
+    if let Some(command) = &args.command {
+        process::exit(run_command(command));
+    }
+    let (Some(old_path), Some(new_path)) = (&args.old_lock, &args.new_lock) else {
+        unreachable!("clap requires both lock files without a subcommand");
+    };
+
+    let old_lock = match load_lockfile(old_path) {
+        Ok(lockfile) => lockfile,
         Err(e) => {
             eprintln!("Error: {}", e);
-            process::exit(2);
+            process::exit(EXIT_ERROR);
         }
     };
 
-    let new_packages = match parse_lockfile(&args.new_lock) {
-        Ok(packages) => packages,
+    let new_lock = match load_lockfile(new_path) {
+        Ok(lockfile) => lockfile,
         Err(e) => {
             eprintln!("Error parsing lock files: {}", e);
-            process::exit(2);
+            process::exit(EXIT_ERROR);
+        }
+    };
+
+    let python_targets = if args.python_versions.is_empty() {
+        new_lock
+            .metadata
+            .python_versions
+            .as_deref()
+            .or(old_lock.metadata.python_versions.as_deref())
+            .map(python_targets)
+            .unwrap_or_default()
+    } else {
+        match args.python_versions.iter().map(|v| v.parse()).collect() {
+            Ok(versions) => versions,
+            Err(e) => {
+                eprintln!("Error: invalid --python-version: {}", e);
+                process::exit(EXIT_ERROR);
+            }
+        }
+    };
+
+    let environment = Environment {
+        python_version: match args.target_python.as_deref().map(str::parse).transpose() {
+            Ok(version) => version,
+            Err(e) => {
+                eprintln!("Error: invalid --target-python: {}", e);
+                process::exit(EXIT_ERROR);
+            }
+        },
+        platform: args.target_platform,
+    };
+
+    let filter_groups =
+        |packages| match filter_by_groups(packages, &args.groups, &args.exclude_groups) {
+            Ok(packages) => packages,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                process::exit(EXIT_ERROR);
+            }
+        };
+    let old_packages = filter_groups(old_lock.packages);
+    let new_packages = filter_groups(new_lock.packages);
+    let old_packages = filter_by_environment(old_packages, &environment);
+    let new_packages = filter_by_environment(new_packages, &environment);
+
+    let mut report = Report::new(&old_packages, &new_packages);
+    report.metadata_changes = compare_metadata(&old_lock.metadata, &new_lock.metadata);
+    report.new_lock = Some(LockLocation {
+        path: new_path.display().to_string(),
+        package_lines: new_lock.package_lines,
+    });
+    report.add_findings(compare_python_support(
+        &old_packages,
+        &new_packages,
+        &python_targets,
+    ));
+    report.add_findings(compare_wheel_coverage(
+        &old_packages,
+        &new_packages,
+        &args.required_wheel_tags,
+    ));
+
+    if let Some(path) = &args.pyproject {
+        let pyproject = load_pyproject(path);
+        report.direct_dependencies = Some(pyproject.direct_dependencies());
+
+        if let Some(old_path) = &args.old_pyproject {
+            let old_pyproject = load_pyproject(old_path);
+            report.constraint_changes = compare_constraints(&old_pyproject, &pyproject);
+            report.add_findings(unexpected_breaking_bumps(
+                &report.bumps,
+                &report.constraint_changes,
+                &pyproject,
+            ));
+        }
+    }
+
+    report.trace_dependencies(&new_packages);
+    report.sort_bumps(args.sort);
+
+    if let Some(path) = &args.write_baseline {
+        let baseline = Baseline::from_changes(&report.bumps, &report.findings);
+        if let Err(e) = write_baseline(path, &baseline) {
+            eprintln!("Error writing baseline: {}", e);
+            process::exit(EXIT_ERROR);
+        }
+        eprintln!(
+            "Wrote baseline with {} entries to {}",
+            baseline.len(),
+            path.display()
+        );
+        return;
+    }
+
+    if let Some(path) = &args.baseline {
+        let baseline = match read_baseline(path) {
+            Ok(baseline) => baseline,
+            Err(e) => {
+                eprintln!("Error reading baseline: {}", e);
+                process::exit(EXIT_ERROR);
+            }
+        };
+
+        let result = baseline.apply(report.bumps, report.findings);
+        let suppressed = result.suppressed.len() + result.suppressed_findings.len();
+        if suppressed > 0 {
+            eprintln!("{} change(s) suppressed by baseline.", suppressed);
+        }
+        for entry in &result.stale {
+            eprintln!(
+                "Baseline entry no longer needed: {}: {} → {}",
+                entry.package, entry.old_version, entry.new_version
+            );
+        }
+        for entry in &result.stale_findings {
+            eprintln!(
+                "Baseline entry no longer needed: {}: {}",
+                entry.package, entry.kind
+            );
+        }
+        report.bumps = result.bumps;
+        report.findings = result.findings;
+    }
+
+    if args.footprint {
+        report.footprint = Some(Footprint::new(&old_packages, &new_packages));
+    }
+
+    for environment in &args.environments {
+        report.add_environment(environment.clone(), &old_packages, &new_packages);
+    }
+
+    let format = if args.json {
+        OutputFormat::Json
+    } else {
+        args.format
+    };
+    println!("{}", format_report(&report, format, args.all));
+
+    let policy = Policy {
+        fail_on: args.fail_on,
+        direct_only: args.direct_only,
+    };
+    process::exit(evaluate(&report, &policy).exit_code());
+}
+
+fn run_command(command: &Command) -> i32 {
+    match command {
+        Command::Check {
+            pyproject,
+            lock,
+            json,
+        } => run_check(pyproject, lock, *json),
+        Command::Lint {
+            pyproject,
+            lock,
+            json,
+        } => run_lint(pyproject, lock, *json),
+        Command::Validate { lock, json } => run_validate(lock, *json),
+    }
+}
+
+fn run_check(pyproject: &Path, lock: &Path, json: bool) -> i32 {
+    let result = match check_lock(pyproject, lock) {
+        Ok(result) => result,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return EXIT_ERROR;
+        }
+    };
+
+    let output = if json {
+        format_check_json(&result)
+    } else {
+        format_check_text(&result)
+    };
+    println!("{}", output);
+
+    if result.is_stale() {
+        EXIT_POLICY_VIOLATION
+    } else {
+        EXIT_OK
+    }
+}
+
+fn run_lint(pyproject: &Path, lock: &Path, json: bool) -> i32 {
+    let pyproject = match parse_pyproject(pyproject) {
+        Ok(pyproject) => pyproject,
+        Err(e) => {
+            eprintln!("Error parsing pyproject.toml: {}", e);
+            return EXIT_ERROR;
+        }
+    };
+    let packages = match parse_lockfile(lock) {
+        Ok(packages) => packages,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return EXIT_ERROR;
         }
     };
 
-    let bumps = compare_packages(&old_packages, &new_packages);
+    let issues = lint_constraints(&pyproject, &packages);
+    let output = if json {
+        format_lint_json(&issues)
+    } else {
+        format_lint_text(&issues)
+    };
+    println!("{}", output);
 
-    let output = if args.json {
-        format_json(&bumps, args.all)
+    if issues.is_empty() {
+        EXIT_OK
     } else {
-        format_text(&bumps, args.all)
+        EXIT_POLICY_VIOLATION
+    }
+}
+
+fn run_validate(lock: &Path, json: bool) -> i32 {
+    let packages = match parse_lockfile(lock) {
+        Ok(packages) => packages,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return EXIT_ERROR;
+        }
     };
 
+    let result = validate_lock(&packages);
+    let output = if json {
+        format_validate_json(&result)
+    } else {
+        format_validate_text(&result)
+    };
     println!("{}", output);
 
-    let breaking_bumps: Vec<_> = bumps.iter().filter(|b| b.is_breaking).collect();
-    if !breaking_bumps.is_empty() {
-        process::exit(1);
+    if result.is_valid() {
+        EXIT_OK
+    } else {
+        EXIT_POLICY_VIOLATION
+    }
+}
+
+fn load_pyproject(path: &Path) -> Pyproject {
+    match parse_pyproject(path) {
+        Ok(pyproject) => pyproject,
+        Err(e) => {
+            eprintln!("Error parsing {}: {}", path.display(), e);
+            process::exit(EXIT_ERROR);
+        }
     }
 }
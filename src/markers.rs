@@ -0,0 +1,369 @@
+use crate::lockfile::{normalize_name, LockedPackage};
+use clap::ValueEnum;
+use pep440_rs::{Version, VersionSpecifier};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
+pub enum Platform {
+    Linux,
+    #[value(alias = "darwin")]
+    Macos,
+    #[value(alias = "win32")]
+    Windows,
+}
+
+impl Platform {
+    fn sys_platform(&self) -> &'static str {
+        match self {
+            Platform::Linux => "linux",
+            Platform::Macos => "darwin",
+            Platform::Windows => "win32",
+        }
+    }
+
+    fn platform_system(&self) -> &'static str {
+        match self {
+            Platform::Linux => "Linux",
+            Platform::Macos => "Darwin",
+            Platform::Windows => "Windows",
+        }
+    }
+
+    fn os_name(&self) -> &'static str {
+        match self {
+            Platform::Windows => "nt",
+            Platform::Linux | Platform::Macos => "posix",
+        }
+    }
+}
+
+/// The environment PEP 508 markers are evaluated against. Markers that refer
+/// to anything left unset are assumed to apply.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Environment {
+    pub python_version: Option<Version>,
+    pub platform: Option<Platform>,
+}
+
+impl Environment {
+    /// Whether neither a Python version nor a platform is set.
+    pub fn is_unconstrained(&self) -> bool {
+        self.python_version.is_none() && self.platform.is_none()
+    }
+
+    fn value(&self, variable: &str) -> Option<String> {
+        match variable {
+            "python_version" => {
+                let release = self.python_version.as_ref()?.release().to_vec();
+                Some(format!("{}.{}", release[0], release.get(1).unwrap_or(&0)))
+            }
+            "python_full_version" => {
+                let version = self.python_version.as_ref()?;
+                let mut release = version.release().to_vec();
+                release.resize(release.len().max(3), 0);
+                Some(
+                    release
+                        .iter()
+                        .map(|n| n.to_string())
+                        .collect::<Vec<_>>()
+                        .join("."),
+                )
+            }
+            "sys_platform" => Some(self.platform?.sys_platform().to_string()),
+            "platform_system" => Some(self.platform?.platform_system().to_string()),
+            "os_name" => Some(self.platform?.os_name().to_string()),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Environment {
+    type Err = String;
+
+    /// Parses `PYTHON:PLATFORM`, e.g. `3.12:linux`; either part may be omitted.
+    fn from_str(environment: &str) -> Result<Self, Self::Err> {
+        let mut parsed = Environment::default();
+        for part in environment.split(':').filter(|p| !p.is_empty()) {
+            if let Ok(platform) = Platform::from_str(part, true) {
+                parsed.platform = Some(platform);
+            } else {
+                let version = Version::from_str(part)
+                    .map_err(|_| format!("invalid environment '{}'", environment))?;
+                parsed.python_version = Some(version);
+            }
+        }
+        if parsed.is_unconstrained() {
+            return Err(format!("invalid environment '{}'", environment));
+        }
+        Ok(parsed)
+    }
+}
+
+impl fmt::Display for Environment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let python = self.python_version.as_ref().map(|v| v.to_string());
+        let platform = self
+            .platform
+            .and_then(|p| p.to_possible_value())
+            .map(|v| v.get_name().to_string());
+        let parts: Vec<String> = python.into_iter().chain(platform).collect();
+        f.write_str(&parts.join(":"))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Variable(String),
+    Literal(String),
+    Operator(String),
+    And,
+    Or,
+    Open,
+    Close,
+}
+
+/// Evaluates a PEP 508 marker expression such as
+/// `python_version < "3.11" and sys_platform == "win32"`. Comparisons that
+/// cannot be decided, and markers that fail to parse, count as true.
+pub fn evaluate(markers: &str, environment: &Environment) -> bool {
+    let Some(tokens) = tokenize(markers) else {
+        return true;
+    };
+    let mut parser = Parser {
+        tokens: &tokens,
+        position: 0,
+        environment,
+    };
+    match parser.expression() {
+        Some(result) if parser.position == tokens.len() => result,
+        _ => true,
+    }
+}
+
+fn tokenize(markers: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let chars: Vec<char> = markers.chars().collect();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '(' {
+            tokens.push(Token::Open);
+            i += 1;
+        } else if c == ')' {
+            tokens.push(Token::Close);
+            i += 1;
+        } else if c == '"' || c == '\'' {
+            let end = chars[i + 1..].iter().position(|&ch| ch == c)? + i + 1;
+            tokens.push(Token::Literal(chars[i + 1..end].iter().collect()));
+            i = end + 1;
+        } else if "=!<>~".contains(c) {
+            let start = i;
+            while i < chars.len() && "=!<>~".contains(chars[i]) {
+                i += 1;
+            }
+            tokens.push(Token::Operator(chars[start..i].iter().collect()));
+        } else if c.is_ascii_alphanumeric() || c == '_' || c == '.' {
+            let start = i;
+            while i < chars.len()
+                && (chars[i].is_ascii_alphanumeric() || chars[i] == '_' || chars[i] == '.')
+            {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            match word.as_str() {
+                "and" => tokens.push(Token::And),
+                "or" => tokens.push(Token::Or),
+                "in" => tokens.push(Token::Operator("in".to_string())),
+                "not" => tokens.push(Token::Operator("not".to_string())),
+                _ => tokens.push(Token::Variable(word)),
+            }
+        } else {
+            return None;
+        }
+    }
+
+    // Merge `not in` into a single operator.
+    let mut merged: Vec<Token> = Vec::new();
+    for token in tokens {
+        if token == Token::Operator("in".to_string())
+            && merged.last() == Some(&Token::Operator("not".to_string()))
+        {
+            merged.pop();
+            merged.push(Token::Operator("not in".to_string()));
+        } else {
+            merged.push(token);
+        }
+    }
+
+    Some(merged)
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+    environment: &'a Environment,
+}
+
+impl Parser<'_> {
+    fn next(&mut self) -> Option<&Token> {
+        let token = self.tokens.get(self.position);
+        self.position += 1;
+        token
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn expression(&mut self) -> Option<bool> {
+        let mut result = self.conjunction()?;
+        while self.peek() == Some(&Token::Or) {
+            self.position += 1;
+            let right = self.conjunction()?;
+            result = result || right;
+        }
+        Some(result)
+    }
+
+    fn conjunction(&mut self) -> Option<bool> {
+        let mut result = self.atom()?;
+        while self.peek() == Some(&Token::And) {
+            self.position += 1;
+            let right = self.atom()?;
+            result = result && right;
+        }
+        Some(result)
+    }
+
+    fn atom(&mut self) -> Option<bool> {
+        if self.peek() == Some(&Token::Open) {
+            self.position += 1;
+            let result = self.expression()?;
+            return (self.next() == Some(&Token::Close)).then_some(result);
+        }
+
+        let left = self.next()?.clone();
+        let Some(Token::Operator(operator)) = self.next().cloned() else {
+            return None;
+        };
+        let right = self.next()?.clone();
+
+        Some(match (left, right) {
+            (Token::Variable(variable), Token::Literal(literal)) => {
+                match self.environment.value(&variable) {
+                    Some(value) => compare(&variable, &value, &operator, &literal),
+                    None => true,
+                }
+            }
+            (Token::Literal(literal), Token::Variable(variable)) => {
+                match self.environment.value(&variable) {
+                    Some(value) => compare_reversed(&literal, &operator, &value),
+                    None => true,
+                }
+            }
+            _ => return None,
+        })
+    }
+}
+
+/// Compares an environment value on the left against a marker literal.
+fn compare(variable: &str, value: &str, operator: &str, literal: &str) -> bool {
+    match operator {
+        "in" => literal.contains(value),
+        "not in" => !literal.contains(value),
+        _ if variable.ends_with("_version") => compare_versions(value, operator, literal),
+        "==" | "===" => value == literal,
+        "!=" => value != literal,
+        _ => true,
+    }
+}
+
+/// Compares a marker literal on the left against an environment value.
+fn compare_reversed(literal: &str, operator: &str, value: &str) -> bool {
+    match operator {
+        "in" => value.contains(literal),
+        "not in" => !value.contains(literal),
+        "==" | "===" => value == literal,
+        "!=" => value != literal,
+        _ => true,
+    }
+}
+
+fn compare_versions(value: &str, operator: &str, literal: &str) -> bool {
+    let (Ok(version), Ok(specifier)) = (
+        Version::from_str(value),
+        VersionSpecifier::from_str(&format!("{}{}", operator, literal)),
+    ) else {
+        return compare_reversed(literal, operator, value);
+    };
+    specifier.contains(&version)
+}
+
+/// Keeps the packages installed in `environment`: those whose own markers
+/// match and that are still required by an installed package through a
+/// requirement whose markers match. Packages nothing depends on are kept.
+pub fn filter_by_environment(
+    packages: HashMap<String, LockedPackage>,
+    environment: &Environment,
+) -> HashMap<String, LockedPackage> {
+    if environment.is_unconstrained() {
+        return packages;
+    }
+
+    let mut installed: HashSet<String> = packages
+        .iter()
+        .filter(|(_, package)| {
+            package
+                .markers
+                .as_deref()
+                .is_none_or(|markers| evaluate(markers, environment))
+        })
+        .map(|(name, _)| name.clone())
+        .collect();
+
+    let required_by_anything: HashSet<String> = packages
+        .iter()
+        .flat_map(|(name, package)| {
+            package
+                .dependencies
+                .iter()
+                .map(|d| normalize_name(&d.name))
+                .filter(move |d| d != name)
+        })
+        .collect();
+
+    // Drop packages that lost every installed dependent, until nothing changes.
+    loop {
+        let required: HashSet<String> = installed
+            .iter()
+            .flat_map(|name| {
+                packages[name]
+                    .dependencies
+                    .iter()
+                    .filter(|d| {
+                        d.markers
+                            .as_deref()
+                            .is_none_or(|markers| evaluate(markers, environment))
+                    })
+                    .map(|d| normalize_name(&d.name))
+                    .filter(move |d| d != name)
+            })
+            .collect();
+
+        let before = installed.len();
+        installed.retain(|name| !required_by_anything.contains(name) || required.contains(name));
+        if installed.len() == before {
+            break;
+        }
+    }
+
+    packages
+        .into_iter()
+        .filter(|(name, _)| installed.contains(name))
+        .collect()
+}
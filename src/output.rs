@@ -1,14 +1,79 @@
-use crate::compare::VersionBump;
+use crate::check::CheckResult;
+use crate::compare::{
+    is_minor_bump, ConstraintChange, ConstraintChangeKind, MetadataChange, VersionBump,
+};
+use crate::findings::{Finding, FindingKind, Severity};
+use crate::footprint::{format_size, format_size_delta, Footprint, SizeChange};
+use crate::lint::LintIssue;
+use crate::lockfile::{normalize_name, LockedPackage};
+use crate::report::Report;
+use crate::validate::{UnmetRequirement, ValidationResult};
+use clap::ValueEnum;
+use pep440_rs::Version;
 use serde::Serialize;
+use std::collections::HashMap;
 
-pub fn format_text(bumps: &[VersionBump], all: bool) -> String {
+/// Number of size changes listed without `all`.
+const MAX_SIZE_CHANGES: usize = 10;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Plain text for the terminal
+    #[default]
+    Text,
+    /// JSON for scripts
+    Json,
+    /// Markdown for pull request comments
+    Markdown,
+    /// SARIF 2.1.0 for code scanning
+    Sarif,
+    /// JUnit XML for CI test reports
+    Junit,
+}
+
+pub fn format_report(report: &Report, format: OutputFormat, all: bool) -> String {
+    match format {
+        OutputFormat::Text => format_text(report, all),
+        OutputFormat::Json => format_json(report, all),
+        OutputFormat::Markdown => format_markdown(report, all),
+        OutputFormat::Sarif => format_sarif(report, all),
+        OutputFormat::Junit => format_junit(report, all),
+    }
+}
+
+pub fn format_text(report: &Report, all: bool) -> String {
+    let mut output = format_changes(report, all);
+    if !report.constraint_changes.is_empty() {
+        output.push_str("\n\n");
+        output.push_str(&format_constraint_changes(report));
+    }
+    if !report.metadata_changes.is_empty() {
+        output.push_str("\n\n");
+        output.push_str(&format_metadata_changes(&report.metadata_changes));
+    }
+    if let Some(footprint) = &report.footprint {
+        output.push_str("\n\n");
+        output.push_str(&format_footprint(footprint, all));
+    }
+    output
+}
+
+fn format_changes(report: &Report, all: bool) -> String {
+    let bumps = &report.bumps;
     let breaking_bumps: Vec<&VersionBump> = bumps.iter().filter(|b| b.is_breaking).collect();
     let non_breaking_bumps: Vec<&VersionBump> = bumps.iter().filter(|b| !b.is_breaking).collect();
 
-    if breaking_bumps.is_empty() && non_breaking_bumps.is_empty() {
+    let findings = visible_findings(report, all);
+
+    let has_other_changes = !non_breaking_bumps.is_empty()
+        || !report.added.is_empty()
+        || !report.removed.is_empty()
+        || !report.findings.is_empty();
+
+    if breaking_bumps.is_empty() && findings.is_empty() && !has_other_changes {
         return "No breaking changes detected.".to_string();
     }
-    if breaking_bumps.is_empty() && !all {
+    if breaking_bumps.is_empty() && findings.is_empty() && !all {
         return "No breaking changes detected.".to_string();
     }
 
@@ -16,18 +81,22 @@ pub fn format_text(bumps: &[VersionBump], all: bool) -> String {
 
     if !breaking_bumps.is_empty() {
         lines.push("Breaking changes detected:".to_string());
-        for bump in &breaking_bumps {
-            let change_type = if bump.new_version.starts_with('0') {
-                "0.x MINOR"
-            } else {
-                "MAJOR"
-            };
-            let version_info = format!("{} → {}", bump.old_version, bump.new_version);
-            lines.push(format!(
-                "  {}: {} ({})",
-                bump.package_name, version_info, change_type
-            ));
-        }
+        let entries = breaking_bumps
+            .iter()
+            .map(|bump| {
+                let version_info = format!("{} → {}", bump.old_version, bump.new_version);
+                let mut entry = vec![format!(
+                    "{}: {} ({}){}",
+                    bump.package_name,
+                    version_info,
+                    change_type(bump),
+                    dependents_summary(bump)
+                )];
+                entry.extend(path_lines(bump));
+                (bump.package_name.as_str(), entry)
+            })
+            .collect();
+        push_entries(&mut lines, report, entries);
         lines.push(format!(
             "{} breaking change(s) found.",
             breaking_bumps.len()
@@ -35,56 +104,1298 @@ pub fn format_text(bumps: &[VersionBump], all: bool) -> String {
     }
 
     if all && !non_breaking_bumps.is_empty() {
-        if !breaking_bumps.is_empty() {
+        if !lines.is_empty() {
             lines.push(String::new());
         }
         lines.push("Non-breaking changes:".to_string());
-        for bump in &non_breaking_bumps {
+        let entries = non_breaking_bumps
+            .iter()
+            .map(|bump| {
+                let mut entry = vec![format!(
+                    "{}: {} → {}",
+                    bump.package_name, bump.old_version, bump.new_version
+                )];
+                entry.extend(path_lines(bump));
+                (bump.package_name.as_str(), entry)
+            })
+            .collect();
+        push_entries(&mut lines, report, entries);
+    }
+
+    push_environment_sections(&mut lines, report, all);
+
+    if all {
+        push_package_section(&mut lines, report, "Added packages:", &report.added);
+        push_package_section(&mut lines, report, "Removed packages:", &report.removed);
+    }
+
+    if !findings.is_empty() {
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines.push("Findings:".to_string());
+        let entries = findings
+            .iter()
+            .map(|f| {
+                (
+                    f.package_name.as_str(),
+                    vec![format!(
+                        "{}: {} ({}, {})",
+                        f.package_name, f.message, f.kind, f.severity
+                    )],
+                )
+            })
+            .collect();
+        push_entries(&mut lines, report, entries);
+    }
+
+    lines.join("\n")
+}
+
+fn format_constraint_changes(report: &Report) -> String {
+    let describe = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_string());
+
+    let mut lines = vec!["Constraint changes:".to_string()];
+    for change in &report.constraint_changes {
+        let mut line = format!(
+            "  {} ({}): {} → {} ({})",
+            change.package_name,
+            change.group,
+            describe(&change.old_constraint),
+            describe(&change.new_constraint),
+            change.kind
+        );
+        if let Some(bump) = constraint_bump(report, change) {
+            line.push_str(&format!(
+                "; locked {} → {}",
+                bump.old_version, bump.new_version
+            ));
+        }
+        lines.push(line);
+    }
+
+    lines.join("\n")
+}
+
+/// The version bump of the package whose constraint changed, if any.
+fn constraint_bump<'a>(report: &'a Report, change: &ConstraintChange) -> Option<&'a VersionBump> {
+    let name = normalize_name(&change.package_name);
+    report
+        .bumps
+        .iter()
+        .find(|b| normalize_name(&b.package_name) == name)
+}
+
+fn format_metadata_changes(changes: &[MetadataChange]) -> String {
+    let describe = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_string());
+
+    let mut lines = vec!["Lock metadata:".to_string()];
+    lines.extend(changes.iter().map(|change| {
+        format!(
+            "  {}: {} → {}",
+            change.field,
+            describe(&change.old_value),
+            describe(&change.new_value)
+        )
+    }));
+
+    lines.join("\n")
+}
+
+fn format_footprint(footprint: &Footprint, all: bool) -> String {
+    let count_line = |label: &str, old: usize, new: usize| {
+        format!(
+            "  {}: {} → {} ({:+})",
+            label,
+            old,
+            new,
+            new as i64 - old as i64
+        )
+    };
+
+    let mut lines = vec![
+        "Footprint:".to_string(),
+        count_line("Packages", footprint.old_packages, footprint.new_packages),
+        count_line(
+            "Artifacts",
+            footprint.old_artifacts,
+            footprint.new_artifacts,
+        ),
+    ];
+
+    if footprint.old_size.is_some() || footprint.new_size.is_some() {
+        let old_size = footprint.old_size.unwrap_or(0);
+        let new_size = footprint.new_size.unwrap_or(0);
+        lines.push(format!(
+            "  Size: {} → {} ({})",
+            format_size(old_size),
+            format_size(new_size),
+            format_size_delta(new_size as i64 - old_size as i64)
+        ));
+    }
+
+    if !footprint.size_changes.is_empty() {
+        lines.push("  Size changes:".to_string());
+        let shown = if all {
+            footprint.size_changes.len()
+        } else {
+            MAX_SIZE_CHANGES
+        };
+        for change in footprint.size_changes.iter().take(shown) {
             lines.push(format!(
-                "  {}: {} → {}",
-                bump.package_name, bump.old_version, bump.new_version
+                "    {}: {} → {} ({})",
+                change.package_name,
+                describe_size(change.old_size),
+                describe_size(change.new_size),
+                format_size_delta(change.delta())
             ));
         }
+        let hidden = footprint.size_changes.len().saturating_sub(shown);
+        if hidden > 0 {
+            lines.push(format!("    ... and {} more (use --all)", hidden));
+        }
     }
 
     lines.join("\n")
 }
 
+fn describe_size(size: Option<u64>) -> String {
+    size.map(format_size).unwrap_or_else(|| "-".to_string())
+}
+
+/// High-severity findings are always reported; the rest only with `all`.
+fn visible_findings(report: &Report, all: bool) -> Vec<&Finding> {
+    report
+        .findings
+        .iter()
+        .filter(|f| all || f.severity == Severity::High)
+        .collect()
+}
+
+fn change_type(bump: &VersionBump) -> &'static str {
+    if bump.new_version.starts_with('0') {
+        "0.x MINOR"
+    } else {
+        "MAJOR"
+    }
+}
+
+/// Lists, per matrix environment, the bumps that apply there: breaking ones
+/// always, non-breaking ones with `all`.
+fn push_environment_sections(lines: &mut Vec<String>, report: &Report, all: bool) {
+    if report.environments.is_empty() {
+        return;
+    }
+    if !lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines.push("Environments:".to_string());
+
+    for environment in &report.environments {
+        let breaking_count = environment.bumps.iter().filter(|b| b.is_breaking).count();
+        let summary = match breaking_count {
+            0 => "no breaking changes".to_string(),
+            n => format!("{} breaking change(s)", n),
+        };
+        lines.push(format!("  {}: {}", environment.environment, summary));
+
+        for bump in environment.bumps.iter().filter(|b| all || b.is_breaking) {
+            let mut line = format!(
+                "    {}: {} → {}",
+                bump.package_name, bump.old_version, bump.new_version
+            );
+            if bump.is_breaking {
+                line.push_str(&format!(" ({})", change_type(bump)));
+            }
+            lines.push(line);
+        }
+    }
+}
+
+fn push_package_section(
+    lines: &mut Vec<String>,
+    report: &Report,
+    heading: &str,
+    packages: &[LockedPackage],
+) {
+    if packages.is_empty() {
+        return;
+    }
+    if !lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines.push(heading.to_string());
+    let entries = packages
+        .iter()
+        .map(|p| (p.name.as_str(), vec![format!("{}: {}", p.name, p.version)]))
+        .collect();
+    push_entries(lines, report, entries);
+}
+
+fn dependents_summary(bump: &VersionBump) -> String {
+    if bump.dependents.is_empty() {
+        return String::new();
+    }
+    format!(
+        " [{} dependent(s), {} direct]",
+        bump.dependents.len(),
+        bump.direct_dependents.len()
+    )
+}
+
+/// Dependency chains leading to a bumped package. A package that is itself a
+/// root has nothing to explain, so single-element paths are omitted.
+fn path_lines(bump: &VersionBump) -> Vec<String> {
+    bump.paths
+        .iter()
+        .filter(|path| path.len() > 1)
+        .map(|path| format!("  via {}", path.join(" → ")))
+        .collect()
+}
+
+/// Pushes `(package_name, lines)` entries, grouped into direct and transitive
+/// dependencies when the report knows the project's direct dependencies.
+fn push_entries(lines: &mut Vec<String>, report: &Report, entries: Vec<(&str, Vec<String>)>) {
+    if report.direct_dependencies.is_none() {
+        for (_, entry) in entries {
+            lines.extend(entry.iter().map(|line| format!("  {}", line)));
+        }
+        return;
+    }
+
+    let (direct, transitive): (Vec<_>, Vec<_>) = entries
+        .into_iter()
+        .partition(|(name, _)| report.is_direct(name) == Some(true));
+
+    for (heading, group) in [
+        ("Direct dependencies:", direct),
+        ("Transitive dependencies:", transitive),
+    ] {
+        if group.is_empty() {
+            continue;
+        }
+        lines.push(format!("  {}", heading));
+        for (_, entry) in group {
+            lines.extend(entry.iter().map(|line| format!("    {}", line)));
+        }
+    }
+}
+
 #[derive(Serialize)]
 struct PackageChange {
     package: String,
     old_version: String,
     new_version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    direct: Option<bool>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    paths: Vec<Vec<String>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    direct_dependents: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    dependents: Vec<String>,
+    dependent_count: usize,
 }
 
-pub fn format_json(bumps: &[VersionBump], all: bool) -> String {
-    let breaking_bumps: Vec<&VersionBump> = bumps.iter().filter(|b| b.is_breaking).collect();
-    let non_breaking_bumps: Vec<&VersionBump> = bumps.iter().filter(|b| !b.is_breaking).collect();
+#[derive(Serialize)]
+struct EnvironmentEntry {
+    environment: String,
+    breaking_changes: Vec<PackageChange>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    non_breaking_changes: Option<Vec<PackageChange>>,
+}
+
+#[derive(Serialize)]
+struct ConstraintChangeEntry {
+    package: String,
+    group: String,
+    old_constraint: Option<String>,
+    new_constraint: Option<String>,
+    kind: ConstraintChangeKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    old_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    new_version: Option<String>,
+}
+
+#[derive(Serialize)]
+struct MetadataChangeEntry {
+    field: &'static str,
+    old: Option<String>,
+    new: Option<String>,
+}
+
+#[derive(Serialize)]
+struct FootprintEntry {
+    old_package_count: usize,
+    new_package_count: usize,
+    old_artifact_count: usize,
+    new_artifact_count: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    old_size: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    new_size: Option<u64>,
+    size_changes: Vec<SizeChangeEntry>,
+}
+
+#[derive(Serialize)]
+struct SizeChangeEntry {
+    package: String,
+    old_size: Option<u64>,
+    new_size: Option<u64>,
+    delta: i64,
+}
 
-    let breaking_changes: Vec<PackageChange> = breaking_bumps
+#[derive(Serialize)]
+struct PackageEntry {
+    package: String,
+    version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    direct: Option<bool>,
+}
+
+#[derive(Serialize)]
+struct FindingEntry {
+    package: String,
+    kind: FindingKind,
+    severity: Severity,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    direct: Option<bool>,
+}
+
+/// Formats the report for GitHub and GitLab comments: a summary line, a table
+/// of breaking changes and findings, and collapsed sections for non-breaking
+/// changes, added or removed packages and the other requested sections.
+pub fn format_markdown(report: &Report, all: bool) -> String {
+    let breaking_bumps: Vec<&VersionBump> = report.bumps.iter().filter(|b| b.is_breaking).collect();
+    let non_breaking_bumps: Vec<&VersionBump> =
+        report.bumps.iter().filter(|b| !b.is_breaking).collect();
+    let findings = visible_findings(report, all);
+    let known_direct = report.direct_dependencies.is_some();
+
+    let mut lines = vec!["## pdrift report".to_string(), String::new()];
+
+    let summary = if breaking_bumps.is_empty() {
+        "No breaking changes detected".to_string()
+    } else {
+        format!("**{} breaking change(s)**", breaking_bumps.len())
+    };
+    lines.push(format!(
+        "{} · {} non-breaking · {} added · {} removed · {} finding(s)",
+        summary,
+        non_breaking_bumps.len(),
+        report.added.len(),
+        report.removed.len(),
+        findings.len()
+    ));
+
+    if !breaking_bumps.is_empty() {
+        lines.push(String::new());
+        lines.push("### Breaking changes".to_string());
+        lines.push(String::new());
+        let mut header = vec!["Package", "Version", "Change", "Dependents"];
+        if known_direct {
+            header.insert(1, "Dependency");
+        }
+        push_table_header(&mut lines, &header);
+        for bump in &breaking_bumps {
+            let dependents = match bump.dependents.len() {
+                0 => "-".to_string(),
+                n => format!("{} ({} direct)", n, bump.direct_dependents.len()),
+            };
+            let mut row = vec![
+                format!("`{}`", bump.package_name),
+                format!("{} → {}", bump.old_version, bump.new_version),
+                badge(change_type(bump), "red"),
+                dependents,
+            ];
+            if known_direct {
+                row.insert(1, dependency_kind(report, &bump.package_name));
+            }
+            push_table_row(&mut lines, &row);
+        }
+    }
+
+    if !findings.is_empty() {
+        lines.push(String::new());
+        lines.push("### Findings".to_string());
+        lines.push(String::new());
+        push_table_header(&mut lines, &["Package", "Kind", "Severity", "Details"]);
+        for finding in &findings {
+            push_table_row(
+                &mut lines,
+                &[
+                    format!("`{}`", finding.package_name),
+                    badge(finding.kind.as_str(), "blue"),
+                    badge(
+                        &finding.severity.to_string(),
+                        severity_color(finding.severity),
+                    ),
+                    finding.message.clone(),
+                ],
+            );
+        }
+    }
+
+    if !non_breaking_bumps.is_empty() {
+        let mut rows = Vec::new();
+        for bump in &non_breaking_bumps {
+            let kind = non_breaking_kind(bump);
+            let color = if kind == "minor" { "yellow" } else { "green" };
+            rows.push(vec![
+                format!("`{}`", bump.package_name),
+                format!("{} → {}", bump.old_version, bump.new_version),
+                badge(kind, color),
+            ]);
+        }
+        push_details(
+            &mut lines,
+            &format!("Non-breaking changes ({})", non_breaking_bumps.len()),
+            &["Package", "Version", "Change"],
+            rows,
+        );
+    }
+
+    for (heading, packages) in [
+        ("Added packages", &report.added),
+        ("Removed packages", &report.removed),
+    ] {
+        if packages.is_empty() {
+            continue;
+        }
+        let rows = packages
+            .iter()
+            .map(|p| vec![format!("`{}`", p.name), p.version.clone()])
+            .collect();
+        push_details(
+            &mut lines,
+            &format!("{} ({})", heading, packages.len()),
+            &["Package", "Version"],
+            rows,
+        );
+    }
+
+    push_markdown_extras(&mut lines, report);
+
+    lines.join("\n")
+}
+
+/// Pushes collapsed sections for the per-environment, constraint, metadata
+/// and footprint parts of the report, when present.
+fn push_markdown_extras(lines: &mut Vec<String>, report: &Report) {
+    let describe = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_string());
+
+    if !report.environments.is_empty() {
+        let list = |bumps: Vec<&VersionBump>| {
+            if bumps.is_empty() {
+                return "-".to_string();
+            }
+            bumps
+                .iter()
+                .map(|b| format!("`{}` {} → {}", b.package_name, b.old_version, b.new_version))
+                .collect::<Vec<_>>()
+                .join("<br>")
+        };
+        let rows = report
+            .environments
+            .iter()
+            .map(|environment| {
+                let (breaking, non_breaking): (Vec<&VersionBump>, Vec<&VersionBump>) =
+                    environment.bumps.iter().partition(|b| b.is_breaking);
+                vec![
+                    format!("`{}`", environment.environment),
+                    list(breaking),
+                    list(non_breaking),
+                ]
+            })
+            .collect();
+        push_details(
+            lines,
+            &format!("Environments ({})", report.environments.len()),
+            &["Environment", "Breaking changes", "Non-breaking changes"],
+            rows,
+        );
+    }
+
+    if !report.constraint_changes.is_empty() {
+        let rows = report
+            .constraint_changes
+            .iter()
+            .map(|change| {
+                let locked = constraint_bump(report, change).map_or("-".to_string(), |b| {
+                    format!("{} → {}", b.old_version, b.new_version)
+                });
+                vec![
+                    format!("`{}`", change.package_name),
+                    change.group.clone(),
+                    describe(&change.old_constraint),
+                    describe(&change.new_constraint),
+                    change.kind.to_string(),
+                    locked,
+                ]
+            })
+            .collect();
+        push_details(
+            lines,
+            &format!("Constraint changes ({})", report.constraint_changes.len()),
+            &["Package", "Group", "Old", "New", "Change", "Locked"],
+            rows,
+        );
+    }
+
+    if !report.metadata_changes.is_empty() {
+        let rows = report
+            .metadata_changes
+            .iter()
+            .map(|change| {
+                vec![
+                    format!("`{}`", change.field),
+                    describe(&change.old_value),
+                    describe(&change.new_value),
+                ]
+            })
+            .collect();
+        push_details(
+            lines,
+            &format!("Lock metadata ({})", report.metadata_changes.len()),
+            &["Field", "Old", "New"],
+            rows,
+        );
+    }
+
+    if let Some(footprint) = &report.footprint {
+        let count_row = |label: &str, old: usize, new: usize| {
+            vec![
+                label.to_string(),
+                old.to_string(),
+                new.to_string(),
+                format!("{:+}", new as i64 - old as i64),
+            ]
+        };
+        let mut rows = vec![
+            count_row("Packages", footprint.old_packages, footprint.new_packages),
+            count_row(
+                "Artifacts",
+                footprint.old_artifacts,
+                footprint.new_artifacts,
+            ),
+        ];
+        if footprint.old_size.is_some() || footprint.new_size.is_some() {
+            let old_size = footprint.old_size.unwrap_or(0);
+            let new_size = footprint.new_size.unwrap_or(0);
+            rows.push(vec![
+                "Size".to_string(),
+                format_size(old_size),
+                format_size(new_size),
+                format_size_delta(new_size as i64 - old_size as i64),
+            ]);
+        }
+        push_details(lines, "Footprint", &["Total", "Old", "New", "Change"], rows);
+
+        if !footprint.size_changes.is_empty() {
+            let rows = footprint
+                .size_changes
+                .iter()
+                .map(|change| {
+                    vec![
+                        format!("`{}`", change.package_name),
+                        describe_size(change.old_size),
+                        describe_size(change.new_size),
+                        format_size_delta(change.delta()),
+                    ]
+                })
+                .collect();
+            push_details(
+                lines,
+                &format!("Size changes ({})", footprint.size_changes.len()),
+                &["Package", "Old", "New", "Change"],
+                rows,
+            );
+        }
+    }
+}
+
+/// A shields.io badge image, e.g. for `MAJOR` or `hash-changed`.
+fn badge(label: &str, color: &str) -> String {
+    let escaped = label
+        .replace('-', "--")
+        .replace('_', "__")
+        .replace(' ', "_");
+    format!(
+        "![{}](https://img.shields.io/badge/{}-{})",
+        label, escaped, color
+    )
+}
+
+fn severity_color(severity: Severity) -> &'static str {
+    match severity {
+        Severity::High => "red",
+        Severity::Medium => "orange",
+        Severity::Low => "lightgrey",
+    }
+}
+
+fn non_breaking_kind(bump: &VersionBump) -> &'static str {
+    match (
+        bump.old_version.parse::<Version>(),
+        bump.new_version.parse::<Version>(),
+    ) {
+        (Ok(old), Ok(new)) if new < old => "downgrade",
+        (Ok(_), Ok(_)) if is_minor_bump(&bump.old_version, &bump.new_version) => "minor",
+        (Ok(_), Ok(_)) => "patch",
+        _ => "changed",
+    }
+}
+
+fn dependency_kind(report: &Report, package_name: &str) -> String {
+    match report.is_direct(package_name) {
+        Some(true) => "direct".to_string(),
+        _ => "transitive".to_string(),
+    }
+}
+
+fn push_table_header(lines: &mut Vec<String>, columns: &[&str]) {
+    lines.push(format!("| {} |", columns.join(" | ")));
+    lines.push(format!("|{}", "---|".repeat(columns.len())));
+}
+
+fn push_table_row(lines: &mut Vec<String>, cells: &[String]) {
+    let cells: Vec<String> = cells.iter().map(|c| c.replace('|', "\\|")).collect();
+    lines.push(format!("| {} |", cells.join(" | ")));
+}
+
+/// Pushes a table inside a collapsed `<details>` block.
+fn push_details(lines: &mut Vec<String>, summary: &str, columns: &[&str], rows: Vec<Vec<String>>) {
+    lines.push(String::new());
+    lines.push("<details>".to_string());
+    lines.push(format!("<summary>{}</summary>", summary));
+    lines.push(String::new());
+    push_table_header(lines, columns);
+    for row in rows {
+        push_table_row(lines, &row);
+    }
+    lines.push(String::new());
+    lines.push("</details>".to_string());
+}
+
+/// SARIF rule for breaking version bumps; findings use their kind as rule ID.
+const BREAKING_BUMP_RULE: &str = "breaking-bump";
+const NON_BREAKING_BUMP_RULE: &str = "non-breaking-bump";
+
+struct SarifEntry<'a> {
+    rule: &'a str,
+    description: &'a str,
+    level: &'a str,
+    package_name: &'a str,
+    message: String,
+}
+
+/// Formats the report as a SARIF 2.1.0 log with one result per breaking bump
+/// and finding (and, with `all`, per non-breaking bump), located at the
+/// package's entry in the new lock file.
+pub fn format_sarif(report: &Report, all: bool) -> String {
+    let mut entries = Vec::new();
+
+    for bump in report.bumps.iter().filter(|b| all || b.is_breaking) {
+        entries.push(if bump.is_breaking {
+            SarifEntry {
+                rule: BREAKING_BUMP_RULE,
+                description: "A package was bumped to a new major version (or 0.x minor version)",
+                level: "error",
+                package_name: &bump.package_name,
+                message: format!(
+                    "{}: {} → {} ({})",
+                    bump.package_name,
+                    bump.old_version,
+                    bump.new_version,
+                    change_type(bump)
+                ),
+            }
+        } else {
+            SarifEntry {
+                rule: NON_BREAKING_BUMP_RULE,
+                description: "A package was bumped within its major version",
+                level: "note",
+                package_name: &bump.package_name,
+                message: format!(
+                    "{}: {} → {}",
+                    bump.package_name, bump.old_version, bump.new_version
+                ),
+            }
+        });
+    }
+
+    for finding in visible_findings(report, all) {
+        entries.push(SarifEntry {
+            rule: finding.kind.as_str(),
+            description: finding.kind.description(),
+            level: match finding.severity {
+                Severity::High => "error",
+                Severity::Medium => "warning",
+                Severity::Low => "note",
+            },
+            package_name: &finding.package_name,
+            message: format!("{}: {}", finding.package_name, finding.message),
+        });
+    }
+
+    let mut rules: Vec<(&str, &str)> = entries.iter().map(|e| (e.rule, e.description)).collect();
+    rules.sort();
+    rules.dedup();
+
+    let results: Vec<serde_json::Value> = entries
         .iter()
-        .map(|b| PackageChange {
-            package: b.package_name.clone(),
-            old_version: b.old_version.clone(),
-            new_version: b.new_version.clone(),
+        .map(|entry| {
+            let mut result = serde_json::json!({
+                "ruleId": entry.rule,
+                "ruleIndex": rules.iter().position(|r| r.0 == entry.rule),
+                "level": entry.level,
+                "message": { "text": entry.message },
+            });
+            if let Some(lock) = &report.new_lock {
+                let mut location = serde_json::json!({
+                    "physicalLocation": {
+                        "artifactLocation": artifact_location(&lock.path),
+                    },
+                });
+                if let Some(line) = lock.package_lines.get(&normalize_name(entry.package_name)) {
+                    location["physicalLocation"]["region"] =
+                        serde_json::json!({ "startLine": line });
+                }
+                result["locations"] = serde_json::json!([location]);
+            }
+            result
+        })
+        .collect();
+
+    let rules: Vec<serde_json::Value> = rules
+        .iter()
+        .map(|(id, description)| {
+            serde_json::json!({
+                "id": id,
+                "shortDescription": { "text": description },
+            })
         })
         .collect();
 
+    let log = serde_json::json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "pdrift",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                },
+            },
+            "results": results,
+        }],
+    });
+
+    serde_json::to_string_pretty(&log).unwrap()
+}
+
+/// A SARIF artifact location for a lock path: relative paths are resolved
+/// against `%SRCROOT%`, absolute ones become `file://` URIs.
+fn artifact_location(path: &str) -> serde_json::Value {
+    let path = path.replace('\\', "/");
+    let is_drive = path.as_bytes().get(1) == Some(&b':')
+        && path.starts_with(|c: char| c.is_ascii_alphabetic());
+    if path.starts_with('/') {
+        serde_json::json!({ "uri": format!("file://{}", encode_uri_path(&path)) })
+    } else if is_drive {
+        serde_json::json!({ "uri": format!("file:///{}", encode_uri_path(&path)) })
+    } else {
+        let path = path.trim_start_matches("./");
+        serde_json::json!({ "uri": encode_uri_path(path), "uriBaseId": "%SRCROOT%" })
+    }
+}
+
+/// Percent-encodes everything in a path except unreserved characters, `/`
+/// and a drive letter's `:`.
+fn encode_uri_path(path: &str) -> String {
+    let mut encoded = String::new();
+    for (i, byte) in path.bytes().enumerate() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                encoded.push(byte as char)
+            }
+            b':' if i == 1 => encoded.push(':'),
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+/// Formats the report as JUnit XML with one testcase per package in either
+/// lock. Breaking bumps and high-severity findings are failures; other
+/// changes, and with `all` lower-severity findings, go to the testcase output.
+pub fn format_junit(report: &Report, all: bool) -> String {
+    let findings = visible_findings(report, all);
+    let suite_name = report
+        .new_lock
+        .as_ref()
+        .map_or("pdrift", |lock| lock.path.as_str());
+
+    let mut bumps: HashMap<String, &VersionBump> = HashMap::new();
+    for bump in &report.bumps {
+        bumps
+            .entry(normalize_name(&bump.package_name))
+            .or_insert(bump);
+    }
+    let mut added: HashMap<String, &LockedPackage> = HashMap::new();
+    for package in &report.added {
+        added
+            .entry(normalize_name(&package.name))
+            .or_insert(package);
+    }
+    let mut removed: HashMap<String, &LockedPackage> = HashMap::new();
+    for package in &report.removed {
+        removed
+            .entry(normalize_name(&package.name))
+            .or_insert(package);
+    }
+    let mut findings_by_package: HashMap<String, Vec<&Finding>> = HashMap::new();
+    for finding in findings {
+        findings_by_package
+            .entry(normalize_name(&finding.package_name))
+            .or_default()
+            .push(finding);
+    }
+
+    let mut testcases = Vec::new();
+    let mut failure_count = 0;
+    for package_name in &report.checked_packages {
+        let name = normalize_name(package_name);
+
+        let mut failures = Vec::new();
+        let mut output = Vec::new();
+        if let Some(bump) = bumps.get(&name) {
+            let description = format!("{} → {}", bump.old_version, bump.new_version);
+            if bump.is_breaking {
+                failures.push((
+                    change_type(bump).to_string(),
+                    format!("breaking bump {} ({})", description, change_type(bump)),
+                ));
+                output.push(format!("old version: {}", bump.old_version));
+                output.push(format!("new version: {}", bump.new_version));
+                output.push(format!("change: {}", change_type(bump)));
+            } else {
+                output.push(format!("{} ({})", description, non_breaking_kind(bump)));
+            }
+        }
+        if let Some(package) = added.get(&name) {
+            output.push(format!("added {}", package.version));
+        }
+        if let Some(package) = removed.get(&name) {
+            output.push(format!("removed {}", package.version));
+        }
+        for finding in findings_by_package.get(&name).into_iter().flatten() {
+            if finding.severity == Severity::High {
+                failures.push((finding.kind.to_string(), finding.message.clone()));
+            }
+            output.push(format!(
+                "{} ({}, {})",
+                finding.message, finding.kind, finding.severity
+            ));
+        }
+
+        let is_direct = report
+            .direct_dependencies
+            .as_ref()
+            .map(|direct| direct.contains(&name));
+        let classname = match is_direct {
+            Some(true) => "pdrift.direct",
+            Some(false) => "pdrift.transitive",
+            None => "pdrift",
+        };
+        let open = format!(
+            "    <testcase classname=\"{}\" name=\"{}\" time=\"0\"",
+            classname,
+            xml_escape(package_name)
+        );
+        let body = xml_escape(&output.join("\n"));
+        if let Some((kind, message)) = failures.first() {
+            failure_count += 1;
+            testcases.push(format!("{}>", open));
+            testcases.push(format!(
+                "      <failure type=\"{}\" message=\"{}\">{}</failure>",
+                xml_escape(kind),
+                xml_escape(message),
+                body
+            ));
+            testcases.push("    </testcase>".to_string());
+        } else if !output.is_empty() {
+            testcases.push(format!("{}>", open));
+            testcases.push(format!("      <system-out>{}</system-out>", body));
+            testcases.push("    </testcase>".to_string());
+        } else {
+            testcases.push(format!("{}/>", open));
+        }
+    }
+
+    let counts = format!(
+        "tests=\"{}\" failures=\"{}\" errors=\"0\"",
+        report.checked_packages.len(),
+        failure_count
+    );
+    let mut lines = vec![
+        r#"<?xml version="1.0" encoding="UTF-8"?>"#.to_string(),
+        format!("<testsuites name=\"pdrift\" {}>", counts),
+        format!(
+            "  <testsuite name=\"{}\" {} skipped=\"0\" time=\"0\">",
+            xml_escape(suite_name),
+            counts
+        ),
+    ];
+    lines.extend(testcases);
+    lines.push("  </testsuite>".to_string());
+    lines.push("</testsuites>".to_string());
+
+    lines.join("\n")
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+pub fn format_json(report: &Report, all: bool) -> String {
+    let bumps = &report.bumps;
+    let breaking_bumps: Vec<&VersionBump> = bumps.iter().filter(|b| b.is_breaking).collect();
+    let non_breaking_bumps: Vec<&VersionBump> = bumps.iter().filter(|b| !b.is_breaking).collect();
+
+    let breaking_changes = package_changes(report, &breaking_bumps);
+
     let mut result = serde_json::json!({
         "breaking_changes": breaking_changes
     });
 
     if all && !non_breaking_bumps.is_empty() {
-        let non_breaking_changes: Vec<PackageChange> = non_breaking_bumps
+        let non_breaking_changes = package_changes(report, &non_breaking_bumps);
+        result["non_breaking_changes"] = serde_json::json!(non_breaking_changes);
+    }
+
+    if !report.environments.is_empty() {
+        let entries: Vec<EnvironmentEntry> = report
+            .environments
             .iter()
-            .map(|b| PackageChange {
-                package: b.package_name.clone(),
-                old_version: b.old_version.clone(),
-                new_version: b.new_version.clone(),
+            .map(|environment| {
+                let (breaking, non_breaking): (Vec<&VersionBump>, Vec<&VersionBump>) =
+                    environment.bumps.iter().partition(|b| b.is_breaking);
+                EnvironmentEntry {
+                    environment: environment.environment.to_string(),
+                    breaking_changes: package_changes(report, &breaking),
+                    non_breaking_changes: all.then(|| package_changes(report, &non_breaking)),
+                }
             })
             .collect();
-        result["non_breaking_changes"] = serde_json::json!(non_breaking_changes);
+        result["environments"] = serde_json::json!(entries);
+    }
+
+    if all && !report.added.is_empty() {
+        result["added_packages"] = serde_json::json!(package_entries(report, &report.added));
+    }
+    if all && !report.removed.is_empty() {
+        result["removed_packages"] = serde_json::json!(package_entries(report, &report.removed));
+    }
+
+    let findings = visible_findings(report, all);
+    if !findings.is_empty() {
+        let entries: Vec<FindingEntry> = findings
+            .iter()
+            .map(|f| FindingEntry {
+                package: f.package_name.clone(),
+                kind: f.kind,
+                severity: f.severity,
+                message: f.message.clone(),
+                direct: report.is_direct(&f.package_name),
+            })
+            .collect();
+        result["findings"] = serde_json::json!(entries);
+    }
+
+    if !report.constraint_changes.is_empty() {
+        let entries: Vec<ConstraintChangeEntry> = report
+            .constraint_changes
+            .iter()
+            .map(|change| {
+                let bump = constraint_bump(report, change);
+                ConstraintChangeEntry {
+                    package: change.package_name.clone(),
+                    group: change.group.clone(),
+                    old_constraint: change.old_constraint.clone(),
+                    new_constraint: change.new_constraint.clone(),
+                    kind: change.kind,
+                    old_version: bump.map(|b| b.old_version.clone()),
+                    new_version: bump.map(|b| b.new_version.clone()),
+                }
+            })
+            .collect();
+        result["constraint_changes"] = serde_json::json!(entries);
+    }
+
+    if !report.metadata_changes.is_empty() {
+        let entries: Vec<MetadataChangeEntry> = report
+            .metadata_changes
+            .iter()
+            .map(|change| MetadataChangeEntry {
+                field: change.field,
+                old: change.old_value.clone(),
+                new: change.new_value.clone(),
+            })
+            .collect();
+        result["metadata_changes"] = serde_json::json!(entries);
+    }
+
+    if let Some(footprint) = &report.footprint {
+        result["footprint"] = serde_json::json!(FootprintEntry {
+            old_package_count: footprint.old_packages,
+            new_package_count: footprint.new_packages,
+            old_artifact_count: footprint.old_artifacts,
+            new_artifact_count: footprint.new_artifacts,
+            old_size: footprint.old_size,
+            new_size: footprint.new_size,
+            size_changes: footprint
+                .size_changes
+                .iter()
+                .map(size_change_entry)
+                .collect(),
+        });
     }
 
     serde_json::to_string_pretty(&result).unwrap()
 }
+
+fn size_change_entry(change: &SizeChange) -> SizeChangeEntry {
+    SizeChangeEntry {
+        package: change.package_name.clone(),
+        old_size: change.old_size,
+        new_size: change.new_size,
+        delta: change.delta(),
+    }
+}
+
+fn package_changes(report: &Report, bumps: &[&VersionBump]) -> Vec<PackageChange> {
+    bumps
+        .iter()
+        .map(|b| PackageChange {
+            package: b.package_name.clone(),
+            old_version: b.old_version.clone(),
+            new_version: b.new_version.clone(),
+            direct: report.is_direct(&b.package_name),
+            paths: b.paths.clone(),
+            direct_dependents: b.direct_dependents.clone(),
+            dependents: b.dependents.clone(),
+            dependent_count: b.dependents.len(),
+        })
+        .collect()
+}
+
+fn package_entries(report: &Report, packages: &[LockedPackage]) -> Vec<PackageEntry> {
+    packages
+        .iter()
+        .map(|p| PackageEntry {
+            package: p.name.clone(),
+            version: p.version.clone(),
+            direct: report.is_direct(&p.name),
+        })
+        .collect()
+}
+
+pub fn format_check_text(result: &CheckResult) -> String {
+    let mut lines = Vec::new();
+
+    match &result.lock_hash {
+        None => lines.push(
+            "The lock file records no content-hash; only declarations were checked.".to_string(),
+        ),
+        Some(hash) if *hash != result.pyproject_hash => lines.push(format!(
+            "The lock file is stale: content-hash {} does not match pyproject.toml ({}).",
+            hash, result.pyproject_hash
+        )),
+        Some(_) => {}
+    }
+
+    if !result.mismatches.is_empty() {
+        lines.push("Declarations not satisfied by the lock file:".to_string());
+        for mismatch in &result.mismatches {
+            lines.push(format!(
+                "  {} {} ({}): {}",
+                mismatch.package_name, mismatch.constraint, mismatch.group, mismatch.problem
+            ));
+        }
+    }
+
+    if !result.is_stale() {
+        lines.push("The lock file is up to date with pyproject.toml.".to_string());
+    }
+
+    lines.join("\n")
+}
+
+#[derive(Serialize)]
+struct MismatchEntry {
+    package: String,
+    group: String,
+    constraint: String,
+    problem: String,
+}
+
+pub fn format_check_json(result: &CheckResult) -> String {
+    let mismatches: Vec<MismatchEntry> = result
+        .mismatches
+        .iter()
+        .map(|m| MismatchEntry {
+            package: m.package_name.clone(),
+            group: m.group.clone(),
+            constraint: m.constraint.clone(),
+            problem: m.problem.clone(),
+        })
+        .collect();
+
+    let output = serde_json::json!({
+        "stale": result.is_stale(),
+        "lock_content_hash": result.lock_hash,
+        "pyproject_content_hash": result.pyproject_hash,
+        "mismatches": mismatches,
+    });
+
+    serde_json::to_string_pretty(&output).unwrap()
+}
+
+pub fn format_lint_text(issues: &[LintIssue]) -> String {
+    if issues.is_empty() {
+        return "No constraints allow a breaking bump.".to_string();
+    }
+
+    let mut lines = vec!["Constraints allowing a breaking bump:".to_string()];
+    for issue in issues {
+        lines.push(format!(
+            "  {} {} ({}): locked {}, allows {}; suggest {} ({})",
+            issue.package_name,
+            issue.constraint,
+            issue.group,
+            issue.locked_version,
+            issue.allowed_version,
+            issue.suggestion,
+            issue.pep440_suggestion
+        ));
+    }
+    lines.push(format!("{} constraint(s) found.", issues.len()));
+
+    lines.join("\n")
+}
+
+#[derive(Serialize)]
+struct LintEntry {
+    package: String,
+    group: String,
+    constraint: String,
+    locked_version: String,
+    allowed_version: String,
+    suggestion: String,
+    pep440_suggestion: String,
+}
+
+pub fn format_lint_json(issues: &[LintIssue]) -> String {
+    let entries: Vec<LintEntry> = issues
+        .iter()
+        .map(|issue| LintEntry {
+            package: issue.package_name.clone(),
+            group: issue.group.clone(),
+            constraint: issue.constraint.clone(),
+            locked_version: issue.locked_version.clone(),
+            allowed_version: issue.allowed_version.clone(),
+            suggestion: issue.suggestion.clone(),
+            pep440_suggestion: issue.pep440_suggestion.clone(),
+        })
+        .collect();
+
+    serde_json::to_string_pretty(&serde_json::json!({ "issues": entries })).unwrap()
+}
+
+pub fn format_validate_text(result: &ValidationResult) -> String {
+    if result.is_valid() && result.cycles.is_empty() {
+        return "The lock file is consistent.".to_string();
+    }
+
+    let mut lines = Vec::new();
+    if !result.missing.is_empty() {
+        lines.push("Requirements missing from the lock file:".to_string());
+        for requirement in &result.missing {
+            lines.push(format!(
+                "  {} {} requires {} {}",
+                requirement.package_name,
+                requirement.package_version,
+                requirement.dependency_name,
+                requirement.constraint
+            ));
+        }
+    }
+    if !result.unsatisfied.is_empty() {
+        lines.push("Requirements not satisfied by the lock file:".to_string());
+        for requirement in &result.unsatisfied {
+            lines.push(format!(
+                "  {} {} requires {} {}, locked {}",
+                requirement.package_name,
+                requirement.package_version,
+                requirement.dependency_name,
+                requirement.constraint,
+                requirement.locked_version.as_deref().unwrap_or("-")
+            ));
+        }
+    }
+    if !result.cycles.is_empty() {
+        lines.push("Dependency cycles:".to_string());
+        for cycle in &result.cycles {
+            lines.push(format!("  {}", cycle.join(" → ")));
+        }
+    }
+    if result.is_valid() {
+        lines.push("The lock file is consistent.".to_string());
+    } else {
+        lines.push(format!("{} problem(s) found.", result.problem_count()));
+    }
+
+    lines.join("\n")
+}
+
+#[derive(Serialize)]
+struct UnmetRequirementEntry {
+    package: String,
+    version: String,
+    dependency: String,
+    constraint: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    locked_version: Option<String>,
+}
+
+pub fn format_validate_json(result: &ValidationResult) -> String {
+    let entries = |requirements: &[UnmetRequirement]| -> Vec<UnmetRequirementEntry> {
+        requirements
+            .iter()
+            .map(|r| UnmetRequirementEntry {
+                package: r.package_name.clone(),
+                version: r.package_version.clone(),
+                dependency: r.dependency_name.clone(),
+                constraint: r.constraint.clone(),
+                locked_version: r.locked_version.clone(),
+            })
+            .collect()
+    };
+
+    let output = serde_json::json!({
+        "valid": result.is_valid(),
+        "missing": entries(&result.missing),
+        "unsatisfied": entries(&result.unsatisfied),
+        "cycles": result.cycles,
+    });
+
+    serde_json::to_string_pretty(&output).unwrap()
+}
@@ -0,0 +1,109 @@
+use crate::compare::is_minor_bump;
+use crate::findings::Severity;
+use crate::report::Report;
+use clap::ValueEnum;
+
+/// No policy violations, breaking changes or high-severity findings were found.
+pub const EXIT_OK: i32 = 0;
+/// The `--fail-on` policy was violated.
+pub const EXIT_POLICY_VIOLATION: i32 = 1;
+/// A lock file or baseline could not be read or parsed.
+pub const EXIT_ERROR: i32 = 2;
+/// Breaking changes or high-severity findings were found, but the policy
+/// allows them.
+pub const EXIT_WARNINGS: i32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum FailOn {
+    /// Fail on breaking version bumps
+    Breaking,
+    /// Fail on breaking and minor version bumps
+    Minor,
+    /// Fail on any version bump, added or removed package
+    AnyChange,
+    /// Fail when a package is added
+    Added,
+    /// Fail when a package is removed
+    Removed,
+    /// Fail on high-severity findings
+    High,
+    /// Never fail, only report
+    Never,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Policy {
+    pub fail_on: Vec<FailOn>,
+    /// Only direct dependencies can violate the policy; changes to transitive
+    /// dependencies are downgraded to warnings.
+    pub direct_only: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Clean,
+    Warnings,
+    Violation,
+}
+
+impl Outcome {
+    pub fn exit_code(self) -> i32 {
+        match self {
+            Outcome::Clean => EXIT_OK,
+            Outcome::Warnings => EXIT_WARNINGS,
+            Outcome::Violation => EXIT_POLICY_VIOLATION,
+        }
+    }
+}
+
+pub fn evaluate(report: &Report, policy: &Policy) -> Outcome {
+    let checked = if policy.direct_only {
+        direct_changes(report)
+    } else {
+        report.clone()
+    };
+
+    if policy.fail_on.iter().any(|rule| violates(&checked, *rule)) {
+        return Outcome::Violation;
+    }
+
+    if report.bumps.iter().any(|b| b.is_breaking) || has_high_severity(report) {
+        return Outcome::Warnings;
+    }
+
+    Outcome::Clean
+}
+
+fn violates(report: &Report, rule: FailOn) -> bool {
+    match rule {
+        FailOn::Breaking => report.bumps.iter().any(|b| b.is_breaking),
+        FailOn::Minor => report
+            .bumps
+            .iter()
+            .any(|b| b.is_breaking || is_minor_bump(&b.old_version, &b.new_version)),
+        FailOn::AnyChange => {
+            !report.bumps.is_empty() || !report.added.is_empty() || !report.removed.is_empty()
+        }
+        FailOn::Added => !report.added.is_empty(),
+        FailOn::Removed => !report.removed.is_empty(),
+        FailOn::High => has_high_severity(report),
+        FailOn::Never => false,
+    }
+}
+
+fn has_high_severity(report: &Report) -> bool {
+    report.findings.iter().any(|f| f.severity == Severity::High)
+}
+
+/// The report restricted to direct dependencies; unknown ones count as direct.
+fn direct_changes(report: &Report) -> Report {
+    let is_direct = |name: &str| report.is_direct(name).unwrap_or(true);
+
+    let mut direct = report.clone();
+    direct.bumps.retain(|b| is_direct(&b.package_name));
+    direct.added.retain(|p| is_direct(&p.name));
+    direct.removed.retain(|p| is_direct(&p.name));
+    direct.findings.retain(|f| is_direct(&f.package_name));
+
+    direct
+}
@@ -0,0 +1,196 @@
+use crate::lockfile::normalize_name;
+use regex::Regex;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeclaredDependency {
+    pub name: String,
+    /// The declared version constraint, `*` when none is given.
+    pub constraint: String,
+    /// The Poetry group, PEP 735 dependency group or PEP 621 extra that
+    /// declares the dependency. Runtime dependencies belong to `main`.
+    pub group: String,
+    /// Installed from git, a local path or a URL rather than a package index,
+    /// so the constraint is `*` and cannot be tightened.
+    pub direct_reference: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Pyproject {
+    pub dependencies: Vec<DeclaredDependency>,
+}
+
+impl Pyproject {
+    /// Normalized names of every declared dependency.
+    pub fn direct_dependencies(&self) -> HashSet<String> {
+        self.dependencies
+            .iter()
+            .map(|d| normalize_name(&d.name))
+            .collect()
+    }
+}
+
+pub fn parse_pyproject(path: &Path) -> Result<Pyproject, Box<dyn std::error::Error>> {
+    let content = fs::read_to_string(path)?;
+    let data: toml::Value = toml::from_str(&content)?;
+
+    let mut dependencies = Vec::new();
+
+    if let Some(project) = data.get("project") {
+        if let Some(requirements) = project.get("dependencies").and_then(|v| v.as_array()) {
+            push_requirements(&mut dependencies, requirements, "main")?;
+        }
+        if let Some(extras) = project
+            .get("optional-dependencies")
+            .and_then(|v| v.as_table())
+        {
+            for (extra, requirements) in extras {
+                if let Some(requirements) = requirements.as_array() {
+                    push_requirements(&mut dependencies, requirements, extra)?;
+                }
+            }
+        }
+    }
+
+    if let Some(groups) = data.get("dependency-groups").and_then(|v| v.as_table()) {
+        for (group, requirements) in groups {
+            if let Some(requirements) = requirements.as_array() {
+                push_requirements(&mut dependencies, requirements, group)?;
+            }
+        }
+    }
+
+    if let Some(poetry) = data.get("tool").and_then(|v| v.get("poetry")) {
+        if let Some(table) = poetry.get("dependencies").and_then(|v| v.as_table()) {
+            push_poetry_dependencies(&mut dependencies, table, "main");
+        }
+        if let Some(table) = poetry.get("dev-dependencies").and_then(|v| v.as_table()) {
+            push_poetry_dependencies(&mut dependencies, table, "dev");
+        }
+        if let Some(groups) = poetry.get("group").and_then(|v| v.as_table()) {
+            for (group, definition) in groups {
+                if let Some(table) = definition.get("dependencies").and_then(|v| v.as_table()) {
+                    push_poetry_dependencies(&mut dependencies, table, group);
+                }
+            }
+        }
+    }
+
+    Ok(Pyproject { dependencies })
+}
+
+fn push_requirements(
+    dependencies: &mut Vec<DeclaredDependency>,
+    requirements: &[toml::Value],
+    group: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    for requirement in requirements.iter().filter_map(|r| r.as_str()) {
+        let (name, constraint) = parse_requirement(requirement)
+            .ok_or_else(|| format!("Invalid dependency specification '{}'", requirement))?;
+        let direct_reference = requirement.split(';').next().unwrap_or("").contains('@');
+        push_dependency(dependencies, name, constraint, group, direct_reference);
+    }
+
+    Ok(())
+}
+
+fn push_poetry_dependencies(
+    dependencies: &mut Vec<DeclaredDependency>,
+    table: &toml::map::Map<String, toml::Value>,
+    group: &str,
+) {
+    for (name, specification) in table {
+        if name == "python" {
+            continue;
+        }
+        push_dependency(
+            dependencies,
+            name.clone(),
+            poetry_constraint(specification),
+            group,
+            is_poetry_direct_reference(specification),
+        );
+    }
+}
+
+/// Poetry and PEP 621 declarations may both list the same dependency, e.g.
+/// when `[tool.poetry.dependencies]` only adds a source to a `[project]`
+/// requirement. The first declaration wins.
+fn push_dependency(
+    dependencies: &mut Vec<DeclaredDependency>,
+    name: String,
+    constraint: String,
+    group: &str,
+    direct_reference: bool,
+) {
+    let normalized_name = normalize_name(&name);
+    if dependencies
+        .iter()
+        .any(|d| d.group == group && normalize_name(&d.name) == normalized_name)
+    {
+        return;
+    }
+
+    dependencies.push(DeclaredDependency {
+        name,
+        constraint,
+        group: group.to_string(),
+        direct_reference,
+    });
+}
+
+/// Whether a Poetry dependency specification points at a git repository, a
+/// local path or a URL, in any of its alternatives.
+fn is_poetry_direct_reference(specification: &toml::Value) -> bool {
+    match specification {
+        toml::Value::Table(table) => ["git", "path", "url"]
+            .iter()
+            .any(|key| table.contains_key(*key)),
+        toml::Value::Array(alternatives) => alternatives.iter().any(is_poetry_direct_reference),
+        _ => false,
+    }
+}
+
+/// Extracts the constraint from a Poetry dependency specification, which may
+/// be a plain string, a table with a `version` key, or an array of tables
+/// with per-marker constraints.
+fn poetry_constraint(specification: &toml::Value) -> String {
+    match specification {
+        toml::Value::String(constraint) => constraint.clone(),
+        toml::Value::Table(table) => table
+            .get("version")
+            .and_then(|v| v.as_str())
+            .unwrap_or("*")
+            .to_string(),
+        toml::Value::Array(alternatives) => {
+            let constraints: Vec<String> = alternatives.iter().map(poetry_constraint).collect();
+            constraints.join(" || ")
+        }
+        _ => "*".to_string(),
+    }
+}
+
+/// Splits a PEP 508 requirement such as `requests[socks] (>=2.0); python_version > "3.8"`
+/// into its name and version specifier.
+pub fn parse_requirement(requirement: &str) -> Option<(String, String)> {
+    let re = Regex::new(r"^\s*([A-Za-z0-9][A-Za-z0-9._-]*)\s*(\[[^\]]*\])?\s*([^;]*)").unwrap();
+    let captures = re.captures(requirement)?;
+
+    let name = captures[1].to_string();
+    let specifier = captures[3].trim();
+    let specifier = specifier
+        .strip_prefix('(')
+        .and_then(|s| s.strip_suffix(')'))
+        .unwrap_or(specifier)
+        .trim();
+
+    let constraint = if specifier.is_empty() || specifier.starts_with('@') {
+        "*".to_string()
+    } else {
+        specifier.to_string()
+    };
+
+    Some((name, constraint))
+}
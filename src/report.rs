@@ -0,0 +1,165 @@
+use crate::compare::{
+    added_packages, compare_dependencies, compare_extras, compare_groups, compare_hashes,
+    compare_optional, compare_packages, compare_sdist_only, compare_sources, compare_wheel_tags,
+    removed_packages, ConstraintChange, MetadataChange, VersionBump,
+};
+use crate::findings::{sort_findings, Finding};
+use crate::footprint::Footprint;
+use crate::graph::DependencyGraph;
+use crate::lockfile::{normalize_name, LockLocation, LockedPackage};
+use crate::markers::{filter_by_environment, Environment};
+use clap::ValueEnum;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum SortBy {
+    /// Sort changes alphabetically by package name
+    #[default]
+    Name,
+    /// Sort changes by the number of packages depending on them, most first
+    Dependents,
+}
+
+/// The version bumps that apply in one environment of a matrix run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvironmentBumps {
+    pub environment: Environment,
+    pub bumps: Vec<VersionBump>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Report {
+    pub bumps: Vec<VersionBump>,
+    /// Display names of every package in either lock, sorted by name.
+    pub checked_packages: Vec<String>,
+    pub added: Vec<LockedPackage>,
+    pub removed: Vec<LockedPackage>,
+    /// Changes other than version bumps, such as dependency requirement edits.
+    pub findings: Vec<Finding>,
+    /// Normalized names of the project's direct dependencies, when known.
+    pub direct_dependencies: Option<HashSet<String>>,
+    /// Per-environment views of `bumps`, in the order environments were added.
+    pub environments: Vec<EnvironmentBumps>,
+    /// Declared constraint changes between the old and new pyproject files.
+    pub constraint_changes: Vec<ConstraintChange>,
+    /// Lock-wide metadata that changed, e.g. the generating Poetry version.
+    pub metadata_changes: Vec<MetadataChange>,
+    /// Package count and size totals, when requested.
+    pub footprint: Option<Footprint>,
+    /// The new lock file and its package lines, for formats that point at them.
+    pub new_lock: Option<LockLocation>,
+}
+
+impl Report {
+    pub fn new(
+        old_packages: &HashMap<String, LockedPackage>,
+        new_packages: &HashMap<String, LockedPackage>,
+    ) -> Self {
+        let mut findings = compare_dependencies(old_packages, new_packages);
+        findings.extend(compare_sources(old_packages, new_packages));
+        findings.extend(compare_hashes(old_packages, new_packages));
+        findings.extend(compare_optional(old_packages, new_packages));
+        findings.extend(compare_extras(old_packages, new_packages));
+        findings.extend(compare_groups(old_packages, new_packages));
+        findings.extend(compare_wheel_tags(old_packages, new_packages));
+        findings.extend(compare_sdist_only(old_packages, new_packages));
+        sort_findings(&mut findings);
+
+        Report {
+            bumps: compare_packages(old_packages, new_packages),
+            checked_packages: checked_packages(old_packages, new_packages),
+            added: added_packages(old_packages, new_packages),
+            removed: removed_packages(old_packages, new_packages),
+            findings,
+            direct_dependencies: None,
+            environments: Vec::new(),
+            constraint_changes: Vec::new(),
+            metadata_changes: Vec::new(),
+            footprint: None,
+            new_lock: None,
+        }
+    }
+
+    pub fn add_findings(&mut self, findings: Vec<Finding>) {
+        self.findings.extend(findings);
+        sort_findings(&mut self.findings);
+    }
+
+    /// Records how each bumped package is reached in `packages` and which
+    /// packages depend on it. Paths start from the direct dependencies when
+    /// known, or from packages nothing else depends on otherwise.
+    pub fn trace_dependencies(&mut self, packages: &HashMap<String, LockedPackage>) {
+        let graph = DependencyGraph::new(packages);
+        let roots = match &self.direct_dependencies {
+            Some(direct) => direct.clone(),
+            None => graph.roots(),
+        };
+
+        for bump in &mut self.bumps {
+            bump.paths = graph.shortest_paths(&bump.package_name, &roots);
+            bump.direct_dependents = graph.direct_dependents(&bump.package_name);
+            bump.dependents = graph.all_dependents(&bump.package_name);
+        }
+    }
+
+    /// Records which of the report's bumps apply in `environment`, keeping
+    /// their current order.
+    pub fn add_environment(
+        &mut self,
+        environment: Environment,
+        old_packages: &HashMap<String, LockedPackage>,
+        new_packages: &HashMap<String, LockedPackage>,
+    ) {
+        let old_installed = filter_by_environment(old_packages.clone(), &environment);
+        let new_installed = filter_by_environment(new_packages.clone(), &environment);
+        let applicable: HashSet<String> = compare_packages(&old_installed, &new_installed)
+            .iter()
+            .map(|b| normalize_name(&b.package_name))
+            .collect();
+
+        let bumps = self
+            .bumps
+            .iter()
+            .filter(|b| applicable.contains(&normalize_name(&b.package_name)))
+            .cloned()
+            .collect();
+        self.environments
+            .push(EnvironmentBumps { environment, bumps });
+    }
+
+    pub fn sort_bumps(&mut self, sort_by: SortBy) {
+        match sort_by {
+            SortBy::Name => self.bumps.sort_by_key(|b| b.package_name.to_lowercase()),
+            SortBy::Dependents => self.bumps.sort_by(|a, b| {
+                b.dependents.len().cmp(&a.dependents.len()).then_with(|| {
+                    a.package_name
+                        .to_lowercase()
+                        .cmp(&b.package_name.to_lowercase())
+                })
+            }),
+        }
+    }
+
+    /// Whether `package_name` is a direct dependency, or `None` when no
+    /// project file was provided.
+    pub fn is_direct(&self, package_name: &str) -> Option<bool> {
+        self.direct_dependencies
+            .as_ref()
+            .map(|direct| direct.contains(&normalize_name(package_name)))
+    }
+}
+
+fn checked_packages(
+    old_packages: &HashMap<String, LockedPackage>,
+    new_packages: &HashMap<String, LockedPackage>,
+) -> Vec<String> {
+    let mut names: Vec<String> = old_packages
+        .iter()
+        .filter(|(package_name, _)| !new_packages.contains_key(*package_name))
+        .chain(new_packages)
+        .map(|(_, package)| package.name.clone())
+        .collect();
+    names.sort_by_key(|name| name.to_lowercase());
+
+    names
+}
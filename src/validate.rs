@@ -0,0 +1,112 @@
+use crate::constraint::Constraint;
+use crate::graph::DependencyGraph;
+use crate::lockfile::{normalize_name, Dependency, LockedPackage};
+use pep440_rs::Version;
+use std::collections::{BTreeMap, HashMap};
+
+/// A locked package's requirement that the lock itself does not meet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnmetRequirement {
+    pub package_name: String,
+    pub package_version: String,
+    pub dependency_name: String,
+    /// The required constraint; alternatives with different markers are
+    /// joined with ` || `.
+    pub constraint: String,
+    /// The locked version of the dependency, `None` when it is not locked.
+    pub locked_version: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationResult {
+    /// Required packages that are missing from the lock.
+    pub missing: Vec<UnmetRequirement>,
+    /// Requirements the locked version of the dependency does not satisfy.
+    pub unsatisfied: Vec<UnmetRequirement>,
+    /// Packages that require each other, each cycle starting and ending with
+    /// the same package. Locks can legally contain cycles, so these are
+    /// reported without making the lock invalid.
+    pub cycles: Vec<Vec<String>>,
+}
+
+impl ValidationResult {
+    pub fn is_valid(&self) -> bool {
+        self.missing.is_empty() && self.unsatisfied.is_empty()
+    }
+
+    pub fn problem_count(&self) -> usize {
+        self.missing.len() + self.unsatisfied.len()
+    }
+}
+
+/// Checks that a lock is consistent with itself: every package's
+/// requirements name a locked package whose version satisfies them. Cycles
+/// are collected alongside.
+///
+/// Optional and marker-guarded requirements are only checked when the
+/// dependency is locked, since Poetry leaves out dependencies whose markers
+/// never apply to the project. When a requirement lists alternatives for
+/// different markers, one of them has to be satisfied. Constraints that
+/// cannot be parsed are skipped.
+pub fn validate_lock(packages: &HashMap<String, LockedPackage>) -> ValidationResult {
+    let mut result = ValidationResult::default();
+
+    for package in packages.values() {
+        let mut requirements: BTreeMap<String, Vec<&Dependency>> = BTreeMap::new();
+        for dependency in &package.dependencies {
+            let dependency_name = normalize_name(&dependency.name);
+            if dependency_name != normalize_name(&package.name) {
+                requirements
+                    .entry(dependency_name)
+                    .or_default()
+                    .push(dependency);
+            }
+        }
+
+        for (dependency_name, alternatives) in requirements {
+            let unmet = |locked_version: Option<&String>| UnmetRequirement {
+                package_name: package.name.clone(),
+                package_version: package.version.clone(),
+                dependency_name: alternatives[0].name.clone(),
+                constraint: alternatives
+                    .iter()
+                    .map(|d| d.constraint.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" || "),
+                locked_version: locked_version.cloned(),
+            };
+
+            match packages.get(&dependency_name) {
+                None if alternatives
+                    .iter()
+                    .all(|d| d.optional || d.markers.is_some()) => {}
+                None => result.missing.push(unmet(None)),
+                Some(locked) => {
+                    let Ok(version) = locked.version.parse::<Version>() else {
+                        continue;
+                    };
+                    let satisfied = alternatives.iter().any(|d| {
+                        d.constraint
+                            .parse::<Constraint>()
+                            .map_or(true, |constraint| constraint.contains(&version))
+                    });
+                    if !satisfied {
+                        result.unsatisfied.push(unmet(Some(&locked.version)));
+                    }
+                }
+            }
+        }
+    }
+
+    let by_name = |a: &UnmetRequirement, b: &UnmetRequirement| {
+        a.package_name
+            .to_lowercase()
+            .cmp(&b.package_name.to_lowercase())
+            .then(a.dependency_name.cmp(&b.dependency_name))
+    };
+    result.missing.sort_by(by_name);
+    result.unsatisfied.sort_by(by_name);
+    result.cycles = DependencyGraph::new(packages).cycles();
+
+    result
+}
@@ -0,0 +1,166 @@
+use pdrift_rs::baseline::{read_baseline, write_baseline, Baseline, BaselineEntry};
+use pdrift_rs::compare::VersionBump;
+use pdrift_rs::findings::{Finding, FindingKind, Severity};
+use tempfile::TempDir;
+
+fn bump(name: &str, old_version: &str, new_version: &str, is_breaking: bool) -> VersionBump {
+    VersionBump {
+        package_name: name.to_string(),
+        old_version: old_version.to_string(),
+        new_version: new_version.to_string(),
+        is_breaking,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_from_bumps_records_every_bump() {
+    let bumps = vec![
+        bump("numpy", "1.26.0", "2.0.0", true),
+        bump("requests", "2.31.0", "2.32.0", false),
+    ];
+
+    let baseline = Baseline::from_bumps(&bumps);
+
+    assert_eq!(baseline.bumps.len(), 2);
+    assert_eq!(baseline.bumps[0].package, "numpy");
+    assert_eq!(baseline.bumps[0].old_version, "1.26.0");
+    assert_eq!(baseline.bumps[0].new_version, "2.0.0");
+}
+
+#[test]
+fn test_apply_suppresses_matching_bumps() {
+    let baseline = Baseline::from_bumps(&[bump("numpy", "1.26.0", "2.0.0", true)]);
+
+    let result = baseline.apply(
+        vec![
+            bump("numpy", "1.26.0", "2.0.0", true),
+            bump("urllib3", "1.26.0", "2.0.0", true),
+        ],
+        Vec::new(),
+    );
+
+    assert_eq!(result.suppressed.len(), 1);
+    assert_eq!(result.suppressed[0].package_name, "numpy");
+    assert_eq!(result.bumps.len(), 1);
+    assert_eq!(result.bumps[0].package_name, "urllib3");
+    assert!(result.stale.is_empty());
+}
+
+#[test]
+fn test_apply_requires_matching_versions() {
+    let baseline = Baseline::from_bumps(&[bump("numpy", "1.26.0", "2.0.0", true)]);
+
+    let result = baseline.apply(vec![bump("numpy", "1.26.0", "2.1.0", true)], Vec::new());
+
+    assert!(result.suppressed.is_empty());
+    assert_eq!(result.bumps.len(), 1);
+    assert_eq!(result.stale.len(), 1);
+}
+
+#[test]
+fn test_apply_matches_normalized_names() {
+    let baseline = Baseline {
+        bumps: vec![BaselineEntry {
+            package: "Django-CORS-Headers".to_string(),
+            old_version: "3.0.0".to_string(),
+            new_version: "4.0.0".to_string(),
+        }],
+        ..Default::default()
+    };
+
+    let result = baseline.apply(
+        vec![bump("django_cors_headers", "3.0.0", "4.0.0", true)],
+        Vec::new(),
+    );
+
+    assert!(result.bumps.is_empty());
+    assert_eq!(result.suppressed.len(), 1);
+}
+
+#[test]
+fn test_apply_reports_stale_entries() {
+    let baseline = Baseline::from_bumps(&[
+        bump("numpy", "1.26.0", "2.0.0", true),
+        bump("fastapi", "0.95.0", "0.96.0", true),
+    ]);
+
+    let result = baseline.apply(vec![bump("numpy", "1.26.0", "2.0.0", true)], Vec::new());
+
+    assert_eq!(result.stale.len(), 1);
+    assert_eq!(result.stale[0].package, "fastapi");
+}
+
+fn finding(name: &str, kind: FindingKind) -> Finding {
+    Finding {
+        package_name: name.to_string(),
+        kind,
+        severity: Severity::High,
+        message: "changed".to_string(),
+    }
+}
+
+#[test]
+fn test_apply_suppresses_matching_findings() {
+    let baseline = Baseline::from_changes(
+        &[],
+        &[
+            finding("Pillow", FindingKind::SdistOnly),
+            finding("fastapi", FindingKind::SourceChanged),
+        ],
+    );
+
+    let mut reworded = finding("pillow", FindingKind::SdistOnly);
+    reworded.message = "only an sdist is published".to_string();
+    let result = baseline.apply(
+        Vec::new(),
+        vec![reworded, finding("pillow", FindingKind::HashChanged)],
+    );
+
+    assert_eq!(result.suppressed_findings.len(), 1);
+    assert_eq!(result.findings.len(), 1);
+    assert_eq!(result.findings[0].kind, FindingKind::HashChanged);
+    assert_eq!(result.stale_findings.len(), 1);
+    assert_eq!(result.stale_findings[0].package, "fastapi");
+}
+
+#[test]
+fn test_reads_baseline_without_findings() {
+    let tmp_dir = TempDir::new().unwrap();
+    let baseline_file = tmp_dir.path().join("pdrift-baseline.json");
+
+    std::fs::write(
+        &baseline_file,
+        r#"{"bumps": [{"package": "numpy", "old_version": "1.26.0", "new_version": "2.0.0"}]}"#,
+    )
+    .unwrap();
+
+    let baseline = read_baseline(&baseline_file).unwrap();
+    assert_eq!(baseline.bumps.len(), 1);
+    assert!(baseline.findings.is_empty());
+}
+
+#[test]
+fn test_write_and_read_round_trip() {
+    let tmp_dir = TempDir::new().unwrap();
+    let baseline_file = tmp_dir.path().join("pdrift-baseline.json");
+
+    let baseline = Baseline::from_changes(
+        &[bump("numpy", "1.26.0", "2.0.0", true)],
+        &[finding("pillow", FindingKind::SdistOnly)],
+    );
+    write_baseline(&baseline_file, &baseline).unwrap();
+
+    let result = read_baseline(&baseline_file).unwrap();
+    assert_eq!(result, baseline);
+}
+
+#[test]
+fn test_read_invalid_baseline() {
+    let tmp_dir = TempDir::new().unwrap();
+    let baseline_file = tmp_dir.path().join("pdrift-baseline.json");
+
+    std::fs::write(&baseline_file, "not json").unwrap();
+
+    assert!(read_baseline(&baseline_file).is_err());
+}
@@ -0,0 +1,154 @@
+use pdrift_rs::check::{check_lock, content_hash};
+use tempfile::TempDir;
+
+const POETRY_PYPROJECT: &str = r#"[tool.poetry]
+name = "demo"
+version = "0.1.0"
+
+[tool.poetry.dependencies]
+python = "^3.10"
+requests = { version = "^2.31", extras = ["socks"] }
+"café" = "*"
+
+[tool.poetry.group.dev.dependencies]
+pytest = "^8.0"
+"#;
+
+const PEP621_PYPROJECT: &str = r#"[project]
+name = "demo"
+version = "0.1.0"
+requires-python = ">=3.10"
+dependencies = ["requests>=2.31,<3", "rich (>=13.0)"]
+
+[project.optional-dependencies]
+cli = ["click>=8"]
+
+[tool.poetry.group.dev.dependencies]
+pytest = "^8.0"
+
+[dependency-groups]
+lint = ["ruff>=0.4"]
+"#;
+
+// Expected hashes were computed with Poetry's algorithm in Python.
+
+#[test]
+fn test_content_hash_for_tool_poetry_project() {
+    let pyproject: toml::Value = toml::from_str(POETRY_PYPROJECT).unwrap();
+
+    assert_eq!(
+        content_hash(&pyproject),
+        "76178154e5a25741eab0ecc5218e8f4a31b65067fe31893af8eafd9d1b7c2ba6"
+    );
+}
+
+#[test]
+fn test_content_hash_for_pep621_project() {
+    let pyproject: toml::Value = toml::from_str(PEP621_PYPROJECT).unwrap();
+
+    assert_eq!(
+        content_hash(&pyproject),
+        "244c6942a0806658b54469490332bd47fb0e25332662ee00080843241862f61e"
+    );
+}
+
+#[test]
+fn test_content_hash_ignores_irrelevant_sections() {
+    let pyproject: toml::Value = toml::from_str(POETRY_PYPROJECT).unwrap();
+    let edited: toml::Value = toml::from_str(&POETRY_PYPROJECT.replace(
+        "version = \"0.1.0\"",
+        "version = \"0.2.0\"\ndescription = \"Demo\"",
+    ))
+    .unwrap();
+
+    assert_eq!(content_hash(&pyproject), content_hash(&edited));
+}
+
+fn write_project(pyproject: &str, lock: &str) -> TempDir {
+    let tmp_dir = TempDir::new().unwrap();
+    std::fs::write(tmp_dir.path().join("pyproject.toml"), pyproject).unwrap();
+    std::fs::write(tmp_dir.path().join("poetry.lock"), lock).unwrap();
+    tmp_dir
+}
+
+const LOCKED_PACKAGES: &str = r#"[[package]]
+name = "requests"
+version = "2.32.0"
+
+[[package]]
+name = "café"
+version = "1.0.0"
+
+[[package]]
+name = "pytest"
+version = "8.1.0"
+"#;
+
+#[test]
+fn test_check_up_to_date_lock() {
+    let lock = format!(
+        "{}\n[metadata]\ncontent-hash = \"76178154e5a25741eab0ecc5218e8f4a31b65067fe31893af8eafd9d1b7c2ba6\"\n",
+        LOCKED_PACKAGES
+    );
+    let project = write_project(POETRY_PYPROJECT, &lock);
+
+    let result = check_lock(
+        &project.path().join("pyproject.toml"),
+        &project.path().join("poetry.lock"),
+    )
+    .unwrap();
+
+    assert!(result.mismatches.is_empty());
+    assert!(!result.is_stale());
+}
+
+#[test]
+fn test_check_stale_lock() {
+    let pyproject =
+        POETRY_PYPROJECT.replace("pytest = \"^8.0\"", "pytest = \"^9.0\"\nmypy = \"*\"");
+    let lock = format!(
+        "{}\n[metadata]\ncontent-hash = \"76178154e5a25741eab0ecc5218e8f4a31b65067fe31893af8eafd9d1b7c2ba6\"\n",
+        LOCKED_PACKAGES
+    );
+    let project = write_project(&pyproject, &lock);
+
+    let result = check_lock(
+        &project.path().join("pyproject.toml"),
+        &project.path().join("poetry.lock"),
+    )
+    .unwrap();
+
+    assert!(result.is_stale());
+    let problems: Vec<(&str, &str, &str)> = result
+        .mismatches
+        .iter()
+        .map(|m| {
+            (
+                m.package_name.as_str(),
+                m.group.as_str(),
+                m.problem.as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        problems,
+        vec![
+            ("mypy", "dev", "not in the lock"),
+            ("pytest", "dev", "locked version 8.1.0 does not satisfy it"),
+        ]
+    );
+}
+
+#[test]
+fn test_check_lock_without_content_hash() {
+    let project = write_project(POETRY_PYPROJECT, LOCKED_PACKAGES);
+
+    let result = check_lock(
+        &project.path().join("pyproject.toml"),
+        &project.path().join("poetry.lock"),
+    )
+    .unwrap();
+
+    assert!(result.lock_hash.is_none());
+    assert!(!result.is_stale());
+}
@@ -1,6 +1,58 @@
-use pdrift_rs::compare::{compare_packages, is_breaking_bump};
-use pdrift_rs::lockfile::LockedPackage;
-use std::collections::HashMap;
+use pdrift_rs::compare::{
+    added_packages, compare_constraints, compare_dependencies, compare_extras, compare_groups,
+    compare_hashes, compare_metadata, compare_optional, compare_packages, compare_python_support,
+    compare_sdist_only, compare_sources, compare_wheel_coverage, compare_wheel_tags,
+    is_breaking_bump, is_minor_bump, next_breaking_version, python_targets, removed_packages,
+    unexpected_breaking_bumps, ConstraintChangeKind, VersionBump,
+};
+use pdrift_rs::findings::{FindingKind, Severity};
+use pdrift_rs::lockfile::{
+    normalize_name, Artifact, Dependency, LockMetadata, LockedPackage, PackageSource,
+};
+use pdrift_rs::pyproject::{DeclaredDependency, Pyproject};
+use pep440_rs::Version;
+use std::collections::{BTreeMap, HashMap};
+
+/// A lock containing just `package`.
+fn lock_with(package: LockedPackage) -> HashMap<String, LockedPackage> {
+    HashMap::from([(normalize_name(&package.name), package)])
+}
+
+fn package(name: &str, version: &str) -> LockedPackage {
+    LockedPackage {
+        name: name.to_string(),
+        version: version.to_string(),
+        ..Default::default()
+    }
+}
+
+fn artifacts(files: &[(&str, &str)]) -> Vec<Artifact> {
+    files
+        .iter()
+        .map(|(file, hash)| Artifact {
+            file: file.to_string(),
+            hash: hash.to_string(),
+            ..Default::default()
+        })
+        .collect()
+}
+
+fn wheels(files: &[&str]) -> Vec<Artifact> {
+    let files: Vec<(&str, &str)> = files.iter().map(|f| (*f, "sha256:aaaa")).collect();
+    artifacts(&files)
+}
+
+fn extras(extras: &[(&str, &[&str])]) -> BTreeMap<String, Vec<String>> {
+    extras
+        .iter()
+        .map(|(extra, requirements)| {
+            (
+                extra.to_string(),
+                requirements.iter().map(|r| r.to_string()).collect(),
+            )
+        })
+        .collect()
+}
 
 // Tests for is_breaking_bump
 
@@ -63,6 +115,27 @@ fn test_non_breaking_downgrade() {
     assert!(!is_breaking_bump("0.5.0", "0.4.0"));
 }
 
+// Tests for is_minor_bump
+
+#[test]
+fn test_minor_bump() {
+    assert!(is_minor_bump("1.1.0", "1.2.0"));
+    assert!(is_minor_bump("0.9.0", "0.10.0"));
+}
+
+#[test]
+fn test_not_minor_bump() {
+    assert!(!is_minor_bump("1.1.0", "1.1.1"));
+    assert!(!is_minor_bump("1.1.0", "2.0.0"));
+    assert!(!is_minor_bump("1.2.0", "1.1.0"));
+}
+
+#[test]
+fn test_minor_bump_with_unparseable_version() {
+    assert!(!is_minor_bump("1.1.0", "not-a-version"));
+    assert!(!is_minor_bump("2023-release", "1.2.0"));
+}
+
 // Tests for compare_packages
 
 #[test]
@@ -73,6 +146,7 @@ fn test_detects_version_bump() {
         LockedPackage {
             name: "urllib3".to_string(),
             version: "1.26.0".to_string(),
+            ..Default::default()
         },
     );
 
@@ -82,6 +156,7 @@ fn test_detects_version_bump() {
         LockedPackage {
             name: "urllib3".to_string(),
             version: "2.0.0".to_string(),
+            ..Default::default()
         },
     );
 
@@ -102,6 +177,7 @@ fn test_detects_non_breaking_bump() {
         LockedPackage {
             name: "requests".to_string(),
             version: "2.31.0".to_string(),
+            ..Default::default()
         },
     );
 
@@ -111,6 +187,7 @@ fn test_detects_non_breaking_bump() {
         LockedPackage {
             name: "requests".to_string(),
             version: "2.32.0".to_string(),
+            ..Default::default()
         },
     );
 
@@ -131,6 +208,7 @@ fn test_handles_new_package() {
         LockedPackage {
             name: "newpkg".to_string(),
             version: "1.0.0".to_string(),
+            ..Default::default()
         },
     );
 
@@ -147,6 +225,7 @@ fn test_handles_removed_package() {
         LockedPackage {
             name: "oldpkg".to_string(),
             version: "1.0.0".to_string(),
+            ..Default::default()
         },
     );
 
@@ -165,6 +244,7 @@ fn test_handles_same_version() {
         LockedPackage {
             name: "pkg".to_string(),
             version: "1.0.0".to_string(),
+            ..Default::default()
         },
     );
 
@@ -174,6 +254,7 @@ fn test_handles_same_version() {
         LockedPackage {
             name: "pkg".to_string(),
             version: "1.0.0".to_string(),
+            ..Default::default()
         },
     );
 
@@ -190,6 +271,7 @@ fn test_multiple_packages() {
         LockedPackage {
             name: "pkg1".to_string(),
             version: "1.0.0".to_string(),
+            ..Default::default()
         },
     );
     old.insert(
@@ -197,6 +279,7 @@ fn test_multiple_packages() {
         LockedPackage {
             name: "pkg2".to_string(),
             version: "2.0.0".to_string(),
+            ..Default::default()
         },
     );
     old.insert(
@@ -204,6 +287,7 @@ fn test_multiple_packages() {
         LockedPackage {
             name: "pkg3".to_string(),
             version: "3.0.0".to_string(),
+            ..Default::default()
         },
     );
 
@@ -213,6 +297,7 @@ fn test_multiple_packages() {
         LockedPackage {
             name: "pkg1".to_string(),
             version: "2.0.0".to_string(),
+            ..Default::default()
         },
     );
     new.insert(
@@ -220,6 +305,7 @@ fn test_multiple_packages() {
         LockedPackage {
             name: "pkg2".to_string(),
             version: "2.1.0".to_string(),
+            ..Default::default()
         },
     );
     new.insert(
@@ -227,6 +313,7 @@ fn test_multiple_packages() {
         LockedPackage {
             name: "pkg3".to_string(),
             version: "3.0.0".to_string(),
+            ..Default::default()
         },
     );
 
@@ -247,6 +334,7 @@ fn test_sorted_by_package_name() {
         LockedPackage {
             name: "zebra".to_string(),
             version: "1.0.0".to_string(),
+            ..Default::default()
         },
     );
     old.insert(
@@ -254,6 +342,7 @@ fn test_sorted_by_package_name() {
         LockedPackage {
             name: "alpha".to_string(),
             version: "1.0.0".to_string(),
+            ..Default::default()
         },
     );
     old.insert(
@@ -261,6 +350,7 @@ fn test_sorted_by_package_name() {
         LockedPackage {
             name: "beta".to_string(),
             version: "1.0.0".to_string(),
+            ..Default::default()
         },
     );
 
@@ -270,6 +360,7 @@ fn test_sorted_by_package_name() {
         LockedPackage {
             name: "zebra".to_string(),
             version: "2.0.0".to_string(),
+            ..Default::default()
         },
     );
     new.insert(
@@ -277,6 +368,7 @@ fn test_sorted_by_package_name() {
         LockedPackage {
             name: "alpha".to_string(),
             version: "2.0.0".to_string(),
+            ..Default::default()
         },
     );
     new.insert(
@@ -284,6 +376,7 @@ fn test_sorted_by_package_name() {
         LockedPackage {
             name: "beta".to_string(),
             version: "2.0.0".to_string(),
+            ..Default::default()
         },
     );
 
@@ -292,3 +385,904 @@ fn test_sorted_by_package_name() {
     let names: Vec<&str> = bumps.iter().map(|b| b.package_name.as_str()).collect();
     assert_eq!(names, vec!["alpha", "beta", "zebra"]);
 }
+
+// Tests for added_packages and removed_packages
+
+#[test]
+fn test_added_and_removed_packages() {
+    let mut old = HashMap::new();
+    old.insert(
+        "shared".to_string(),
+        LockedPackage {
+            name: "shared".to_string(),
+            version: "1.0.0".to_string(),
+            ..Default::default()
+        },
+    );
+    old.insert(
+        "oldpkg".to_string(),
+        LockedPackage {
+            name: "oldpkg".to_string(),
+            version: "1.0.0".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let mut new = HashMap::new();
+    new.insert(
+        "shared".to_string(),
+        LockedPackage {
+            name: "shared".to_string(),
+            version: "2.0.0".to_string(),
+            ..Default::default()
+        },
+    );
+    new.insert(
+        "newpkg".to_string(),
+        LockedPackage {
+            name: "newpkg".to_string(),
+            version: "0.1.0".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let added = added_packages(&old, &new);
+    assert_eq!(added.len(), 1);
+    assert_eq!(added[0].name, "newpkg");
+    assert_eq!(added[0].version, "0.1.0");
+
+    let removed = removed_packages(&old, &new);
+    assert_eq!(removed.len(), 1);
+    assert_eq!(removed[0].name, "oldpkg");
+}
+
+// Tests for compare_dependencies
+
+fn dependency(name: &str, constraint: &str) -> Dependency {
+    Dependency {
+        name: name.to_string(),
+        constraint: constraint.to_string(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_unchanged_dependencies() {
+    let old = lock_with(LockedPackage {
+        dependencies: vec![dependency("urllib3", ">=1.21.1,<3")],
+        ..package("requests", "2.31.0")
+    });
+    let new = lock_with(LockedPackage {
+        dependencies: vec![dependency("urllib3", ">=1.21.1,<3")],
+        ..package("requests", "2.31.0")
+    });
+
+    assert!(compare_dependencies(&old, &new).is_empty());
+}
+
+#[test]
+fn test_detects_added_and_removed_dependencies() {
+    let old = lock_with(LockedPackage {
+        dependencies: vec![dependency("chardet", ">=3.0.2,<6")],
+        ..package("requests", "2.31.0")
+    });
+    let new = lock_with(LockedPackage {
+        dependencies: vec![dependency("charset-normalizer", ">=2,<4")],
+        ..package("requests", "2.31.0")
+    });
+
+    let findings = compare_dependencies(&old, &new);
+
+    assert_eq!(findings.len(), 2);
+    assert_eq!(findings[0].kind, FindingKind::DependencyAdded);
+    assert!(findings[0].message.contains("charset-normalizer >=2,<4"));
+    assert_eq!(findings[1].kind, FindingKind::DependencyRemoved);
+    assert!(findings[1].message.contains("chardet >=3.0.2,<6"));
+    assert!(findings.iter().all(|f| f.package_name == "requests"));
+}
+
+#[test]
+fn test_detects_constraint_change() {
+    let old = lock_with(LockedPackage {
+        dependencies: vec![dependency("urllib3", ">=1.21.1,<2")],
+        ..package("requests", "2.31.0")
+    });
+    let new = lock_with(LockedPackage {
+        dependencies: vec![dependency("urllib3", ">=1.21.1,<3")],
+        ..package("requests", "2.31.0")
+    });
+
+    let findings = compare_dependencies(&old, &new);
+
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].kind, FindingKind::DependencyChanged);
+    assert_eq!(
+        findings[0].message,
+        "requirement changed: urllib3 >=1.21.1,<2 → urllib3 >=1.21.1,<3"
+    );
+}
+
+#[test]
+fn test_detects_marker_and_optional_changes() {
+    let old = lock_with(LockedPackage {
+        dependencies: vec![dependency("pysocks", ">=1.5.6")],
+        ..package("requests", "2.31.0")
+    });
+    let new = lock_with(LockedPackage {
+        dependencies: vec![Dependency {
+            name: "PySocks".to_string(),
+            constraint: ">=1.5.6".to_string(),
+            markers: Some("extra == \"socks\"".to_string()),
+            optional: true,
+            ..Default::default()
+        }],
+        ..package("requests", "2.31.0")
+    });
+
+    let findings = compare_dependencies(&old, &new);
+
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].kind, FindingKind::DependencyChanged);
+    assert!(findings[0]
+        .message
+        .ends_with("PySocks >=1.5.6; extra == \"socks\" (optional)"));
+}
+
+#[test]
+fn test_dependencies_of_unshared_packages_are_ignored() {
+    let old = lock_with(LockedPackage {
+        dependencies: vec![dependency("urllib3", "*")],
+        ..package("requests", "2.31.0")
+    });
+    let new: HashMap<String, LockedPackage> = HashMap::new();
+
+    assert!(compare_dependencies(&old, &new).is_empty());
+}
+
+// Tests for compare_sources
+
+fn git_source(resolved_reference: &str) -> PackageSource {
+    PackageSource {
+        source_type: "git".to_string(),
+        url: "https://github.com/example/mylib.git".to_string(),
+        reference: Some("main".to_string()),
+        resolved_reference: Some(resolved_reference.to_string()),
+    }
+}
+
+#[test]
+fn test_unchanged_source() {
+    let old = lock_with(LockedPackage {
+        source: Some(git_source("aaaa")),
+        ..package("mylib", "1.0.0")
+    });
+    let new = lock_with(LockedPackage {
+        source: Some(git_source("aaaa")),
+        ..package("mylib", "1.0.0")
+    });
+
+    assert!(compare_sources(&old, &new).is_empty());
+    assert!(compare_sources(
+        &lock_with(LockedPackage {
+            source: None,
+            ..package("mylib", "1.0.0")
+        }),
+        &lock_with(LockedPackage {
+            source: None,
+            ..package("mylib", "1.0.0")
+        })
+    )
+    .is_empty());
+}
+
+#[test]
+fn test_detects_move_from_pypi_to_private_index() {
+    let old = lock_with(LockedPackage {
+        source: None,
+        ..package("mylib", "1.0.0")
+    });
+    let new = lock_with(LockedPackage {
+        source: Some(PackageSource {
+            source_type: "legacy".to_string(),
+            url: "https://pypi.internal.example.com/simple".to_string(),
+            reference: Some("internal".to_string()),
+            resolved_reference: None,
+        }),
+        ..package("mylib", "1.0.0")
+    });
+
+    let findings = compare_sources(&old, &new);
+
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].kind, FindingKind::SourceChanged);
+    assert_eq!(findings[0].severity, Severity::High);
+    assert_eq!(
+        findings[0].message,
+        "source changed: PyPI → legacy https://pypi.internal.example.com/simple (internal)"
+    );
+}
+
+#[test]
+fn test_detects_git_commit_change_for_same_version() {
+    let old = lock_with(LockedPackage {
+        source: Some(git_source("aaaa")),
+        ..package("mylib", "1.0.0")
+    });
+    let new = lock_with(LockedPackage {
+        source: Some(git_source("bbbb")),
+        ..package("mylib", "1.0.0")
+    });
+
+    let findings = compare_sources(&old, &new);
+
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].severity, Severity::Medium);
+    assert!(findings[0].message.contains("(main @ aaaa) → git"));
+    assert!(findings[0].message.ends_with("(main @ bbbb)"));
+}
+
+// Tests for compare_hashes
+
+#[test]
+fn test_unchanged_hashes() {
+    let old = lock_with(LockedPackage {
+        files: artifacts(&[("requests-2.31.0.tar.gz", "sha256:aaaa")]),
+        ..package("requests", "2.31.0")
+    });
+    let new = lock_with(LockedPackage {
+        files: artifacts(&[("requests-2.31.0.tar.gz", "sha256:aaaa")]),
+        ..package("requests", "2.31.0")
+    });
+
+    assert!(compare_hashes(&old, &new).is_empty());
+}
+
+#[test]
+fn test_detects_hash_change_for_same_version() {
+    let old = lock_with(LockedPackage {
+        files: artifacts(&[("requests-2.31.0.tar.gz", "sha256:aaaa")]),
+        ..package("requests", "2.31.0")
+    });
+    let new = lock_with(LockedPackage {
+        files: artifacts(&[
+            ("requests-2.31.0.tar.gz", "sha256:ffff"),
+            ("requests-2.31.0-py3-none-any.whl", "sha256:bbbb"),
+        ]),
+        ..package("requests", "2.31.0")
+    });
+
+    let findings = compare_hashes(&old, &new);
+
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].kind, FindingKind::HashChanged);
+    assert_eq!(findings[0].severity, Severity::High);
+    assert_eq!(
+        findings[0].message,
+        "artifacts changed for unchanged version 2.31.0: hash changed for \
+         requests-2.31.0.tar.gz; added requests-2.31.0-py3-none-any.whl"
+    );
+}
+
+#[test]
+fn test_hash_changes_ignored_for_version_bumps() {
+    let old = lock_with(LockedPackage {
+        files: artifacts(&[("requests-2.31.0.tar.gz", "sha256:aaaa")]),
+        ..package("requests", "2.31.0")
+    });
+    let new = lock_with(LockedPackage {
+        files: artifacts(&[("requests-2.32.0.tar.gz", "sha256:bbbb")]),
+        ..package("requests", "2.32.0")
+    });
+
+    assert!(compare_hashes(&old, &new).is_empty());
+}
+
+#[test]
+fn test_hash_changes_ignored_without_files() {
+    let old = lock_with(LockedPackage {
+        files: artifacts(&[]),
+        ..package("requests", "2.31.0")
+    });
+    let new = lock_with(LockedPackage {
+        files: artifacts(&[("requests-2.31.0.tar.gz", "sha256:aaaa")]),
+        ..package("requests", "2.31.0")
+    });
+
+    assert!(compare_hashes(&old, &new).is_empty());
+}
+
+// Tests for python_targets and compare_python_support
+
+fn versions(versions: &[&str]) -> Vec<Version> {
+    versions.iter().map(|v| v.parse().unwrap()).collect()
+}
+
+#[test]
+fn test_python_targets_from_caret_range() {
+    assert_eq!(
+        python_targets("^3.11"),
+        versions(&["3.11", "3.12", "3.13", "3.14", "3.15"])
+    );
+}
+
+#[test]
+fn test_python_targets_from_union_range() {
+    assert_eq!(
+        python_targets(">=2.7,<2.8 || >=3.6,<3.8"),
+        versions(&["2.7", "3.6", "3.7"])
+    );
+}
+
+#[test]
+fn test_python_targets_include_early_python_3() {
+    assert_eq!(
+        python_targets(">=3.4,<3.7"),
+        versions(&["3.4", "3.5", "3.6"])
+    );
+}
+
+#[test]
+fn test_python_targets_stop_at_latest_known_release() {
+    assert_eq!(python_targets(">=3.14"), versions(&["3.14", "3.15"]));
+    assert_eq!(
+        python_targets(">=3.15,<3.18"),
+        versions(&["3.15", "3.16", "3.17"])
+    );
+}
+
+#[test]
+fn test_detects_dropped_python_support() {
+    let old = lock_with(LockedPackage {
+        python_versions: Some(">=3.9".to_string()),
+        ..package("numpy", "1.26.4")
+    });
+    let new = lock_with(LockedPackage {
+        python_versions: Some(">=3.10".to_string()),
+        ..package("numpy", "2.1.0")
+    });
+
+    let findings = compare_python_support(&old, &new, &versions(&["3.9", "3.10", "3.11"]));
+
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].kind, FindingKind::PythonSupportDropped);
+    assert_eq!(findings[0].severity, Severity::High);
+    assert_eq!(
+        findings[0].message,
+        "no longer supports Python 3.9 (python-versions >=3.9 → >=3.10)"
+    );
+}
+
+#[test]
+fn test_python_support_drop_outside_targets_ignored() {
+    let old = lock_with(LockedPackage {
+        python_versions: Some(">=3.9".to_string()),
+        ..package("numpy", "1.26.4")
+    });
+    let new = lock_with(LockedPackage {
+        python_versions: Some(">=3.10".to_string()),
+        ..package("numpy", "2.1.0")
+    });
+
+    let findings = compare_python_support(&old, &new, &versions(&["3.11", "3.12"]));
+
+    assert!(findings.is_empty());
+}
+
+#[test]
+fn test_widened_python_support_ignored() {
+    let old = lock_with(LockedPackage {
+        python_versions: Some(">=3.10".to_string()),
+        ..package("numpy", "1.0.0")
+    });
+    let new = lock_with(LockedPackage {
+        python_versions: Some(">=3.8".to_string()),
+        ..package("numpy", "1.1.0")
+    });
+
+    let findings = compare_python_support(&old, &new, &versions(&["3.9", "3.10"]));
+
+    assert!(findings.is_empty());
+}
+
+// Tests for compare_optional
+
+#[test]
+fn test_detects_optional_package_becoming_required() {
+    let findings = compare_optional(
+        &lock_with(LockedPackage {
+            optional: true,
+            ..package("PySocks", "1.7.1")
+        }),
+        &lock_with(LockedPackage {
+            optional: false,
+            ..package("PySocks", "1.7.1")
+        }),
+    );
+
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].package_name, "PySocks");
+    assert_eq!(findings[0].kind, FindingKind::OptionalChanged);
+    assert_eq!(findings[0].severity, Severity::Medium);
+    assert_eq!(findings[0].message, "became required (optional → required)");
+}
+
+#[test]
+fn test_detects_required_package_becoming_optional() {
+    let findings = compare_optional(
+        &lock_with(LockedPackage {
+            optional: false,
+            ..package("PySocks", "1.7.1")
+        }),
+        &lock_with(LockedPackage {
+            optional: true,
+            ..package("PySocks", "1.7.1")
+        }),
+    );
+
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].severity, Severity::Low);
+}
+
+#[test]
+fn test_unchanged_optional_flag() {
+    assert!(compare_optional(
+        &lock_with(LockedPackage {
+            optional: true,
+            ..package("PySocks", "1.7.1")
+        }),
+        &lock_with(LockedPackage {
+            optional: true,
+            ..package("PySocks", "1.7.1")
+        })
+    )
+    .is_empty());
+}
+
+// Tests for compare_extras
+
+#[test]
+fn test_detects_extras_changes() {
+    let old = lock_with(LockedPackage {
+        extras: extras(&[("socks", &["PySocks (>=1.5.6,!=1.5.7)"]), ("security", &[])]),
+        ..package("requests", "2.31.0")
+    });
+    let new = lock_with(LockedPackage {
+        extras: extras(&[
+            ("socks", &["PySocks (>=1.7)"]),
+            ("use-chardet-on-py3", &["chardet (>=3.0.2,<6)"]),
+        ]),
+        ..package("requests", "2.31.0")
+    });
+
+    let findings = compare_extras(&old, &new);
+    let messages: Vec<(&str, Severity)> = findings
+        .iter()
+        .map(|f| (f.message.as_str(), f.severity))
+        .collect();
+
+    assert_eq!(
+        messages,
+        vec![
+            ("extra 'security' removed: (none)", Severity::Medium),
+            (
+                "extra 'socks' changed: PySocks (>=1.5.6,!=1.5.7) → PySocks (>=1.7)",
+                Severity::Medium
+            ),
+            (
+                "extra 'use-chardet-on-py3' added: chardet (>=3.0.2,<6)",
+                Severity::Low
+            ),
+        ]
+    );
+    assert!(findings
+        .iter()
+        .all(|f| f.kind == FindingKind::ExtrasChanged));
+}
+
+#[test]
+fn test_extras_order_ignored() {
+    let old = lock_with(LockedPackage {
+        extras: extras(&[("test", &["pytest", "coverage"])]),
+        ..package("requests", "2.31.0")
+    });
+    let new = lock_with(LockedPackage {
+        extras: extras(&[("test", &["coverage", "pytest"])]),
+        ..package("requests", "2.31.0")
+    });
+
+    assert!(compare_extras(&old, &new).is_empty());
+}
+
+// Tests for compare_groups
+
+#[test]
+fn test_skips_groups_when_a_lock_records_none() {
+    // A Poetry 1.4 lock with `category` against a Poetry 1.8 lock without it.
+    let old = lock_with(LockedPackage {
+        groups: vec!["dev".to_string()],
+        ..package("pytest", "8.0.0")
+    });
+    let new = lock_with(package("pytest", "8.0.0"));
+
+    assert!(compare_groups(&old, &new).is_empty());
+    assert!(compare_groups(&new, &old).is_empty());
+}
+
+#[test]
+fn test_detects_package_moving_into_main() {
+    let findings = compare_groups(
+        &lock_with(LockedPackage {
+            groups: vec!["dev".to_string()],
+            ..package("rich", "13.7.0")
+        }),
+        &lock_with(LockedPackage {
+            groups: vec!["main".to_string()],
+            ..package("rich", "13.7.0")
+        }),
+    );
+
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].kind, FindingKind::GroupsChanged);
+    assert_eq!(findings[0].severity, Severity::Medium);
+    assert_eq!(findings[0].message, "groups changed: dev → main");
+}
+
+#[test]
+fn test_detects_package_leaving_main() {
+    let findings = compare_groups(
+        &lock_with(LockedPackage {
+            groups: vec!["main".to_string(), "dev".to_string()],
+            ..package("rich", "13.7.0")
+        }),
+        &lock_with(LockedPackage {
+            groups: vec!["dev".to_string()],
+            ..package("rich", "13.7.0")
+        }),
+    );
+
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].severity, Severity::Low);
+    assert_eq!(findings[0].message, "groups changed: dev, main → dev");
+}
+
+#[test]
+fn test_group_order_ignored() {
+    let findings = compare_groups(
+        &lock_with(LockedPackage {
+            groups: vec!["main".to_string(), "dev".to_string()],
+            ..package("rich", "13.7.0")
+        }),
+        &lock_with(LockedPackage {
+            groups: vec!["dev".to_string(), "main".to_string()],
+            ..package("rich", "13.7.0")
+        }),
+    );
+
+    assert!(findings.is_empty());
+}
+
+// Tests for compare_wheel_tags and compare_wheel_coverage
+
+#[test]
+fn test_detects_dropped_wheel_tags() {
+    let old = lock_with(LockedPackage {
+        files: wheels(&[
+            "requests-1.0.0-cp39-cp39-manylinux2014_x86_64.whl",
+            "requests-1.0.0-cp312-cp312-manylinux2014_x86_64.whl",
+        ]),
+        ..package("requests", "1.0.0")
+    });
+    let new = lock_with(LockedPackage {
+        files: wheels(&["requests-1.1.0-cp312-cp312-manylinux_2_28_x86_64.whl"]),
+        ..package("requests", "1.1.0")
+    });
+
+    let findings = compare_wheel_tags(&old, &new);
+
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].kind, FindingKind::WheelTagsChanged);
+    assert_eq!(findings[0].severity, Severity::Medium);
+    assert_eq!(
+        findings[0].message,
+        "wheel tags changed: dropped python cp39; dropped abi cp39; \
+         dropped platform manylinux2014_x86_64; added platform manylinux_2_28_x86_64"
+    );
+}
+
+#[test]
+fn test_added_wheel_tags_are_low_severity() {
+    let old = lock_with(LockedPackage {
+        files: wheels(&["requests-1.0.0.tar.gz"]),
+        ..package("requests", "1.0.0")
+    });
+    let new = lock_with(LockedPackage {
+        files: wheels(&[
+            "requests-1.0.1.tar.gz",
+            "requests-1.0.1-py2.py3-none-any.whl",
+        ]),
+        ..package("requests", "1.0.1")
+    });
+
+    let findings = compare_wheel_tags(&old, &new);
+
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].severity, Severity::Low);
+    assert_eq!(
+        findings[0].message,
+        "wheel tags changed: added python py2, py3; added abi none; added platform any"
+    );
+}
+
+#[test]
+fn test_wheel_tags_ignored_without_new_wheels() {
+    let old = lock_with(LockedPackage {
+        files: wheels(&["requests-1.0.0-py3-none-any.whl"]),
+        ..package("requests", "1.0.0")
+    });
+    let new = lock_with(LockedPackage {
+        files: wheels(&["requests-1.1.0.tar.gz"]),
+        ..package("requests", "1.1.0")
+    });
+
+    assert!(compare_wheel_tags(&old, &new).is_empty());
+}
+
+#[test]
+fn test_detects_lost_wheel_coverage() {
+    let old = lock_with(LockedPackage {
+        files: wheels(&[
+            "requests-1.0.0-cp39-cp39-manylinux2014_x86_64.whl",
+            "requests-1.0.0-cp312-cp312-manylinux2014_x86_64.whl",
+        ]),
+        ..package("requests", "1.0.0")
+    });
+    let new = lock_with(LockedPackage {
+        files: wheels(&["requests-1.1.0-cp312-cp312-manylinux_2_28_x86_64.whl"]),
+        ..package("requests", "1.1.0")
+    });
+    let required = vec![
+        "cp39".to_string(),
+        "cp312".to_string(),
+        "cp312-cp312-manylinux2014_x86_64".to_string(),
+    ];
+
+    let findings = compare_wheel_coverage(&old, &new, &required);
+
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].kind, FindingKind::WheelCoverageLost);
+    assert_eq!(findings[0].severity, Severity::High);
+    assert_eq!(
+        findings[0].message,
+        "no longer ships a wheel for cp39, cp312-cp312-manylinux2014_x86_64 (1.0.0 → 1.1.0)"
+    );
+}
+
+#[test]
+fn test_pure_python_wheels_cover_every_tag() {
+    let old = lock_with(LockedPackage {
+        files: wheels(&["requests-1.0.0-cp39-cp39-win_amd64.whl"]),
+        ..package("requests", "1.0.0")
+    });
+    let new = lock_with(LockedPackage {
+        files: wheels(&["requests-1.1.0-py3-none-any.whl"]),
+        ..package("requests", "1.1.0")
+    });
+
+    let findings = compare_wheel_coverage(&old, &new, &["win_amd64".to_string()]);
+
+    assert!(findings.is_empty());
+}
+
+#[test]
+fn test_wheel_coverage_skips_packages_without_artifacts() {
+    let old = lock_with(LockedPackage {
+        files: wheels(&["mylib-1.0.0-cp310-cp310-manylinux2014_x86_64.whl"]),
+        ..package("mylib", "1.0.0")
+    });
+    let new = lock_with(LockedPackage {
+        source: Some(git_source("abc123")),
+        ..package("mylib", "1.1.0")
+    });
+
+    assert!(compare_wheel_coverage(&old, &new, &["cp310".to_string()]).is_empty());
+}
+
+// Tests for compare_sdist_only
+
+#[test]
+fn test_detects_sdist_only_regression() {
+    let old = lock_with(LockedPackage {
+        files: wheels(&[
+            "requests-1.0.0-cp312-cp312-manylinux2014_x86_64.whl",
+            "requests-1.0.0-cp312-cp312-win_amd64.whl",
+            "requests-1.0.0.tar.gz",
+        ]),
+        ..package("requests", "1.0.0")
+    });
+    let new = lock_with(LockedPackage {
+        files: wheels(&["requests-1.1.0.tar.gz"]),
+        ..package("requests", "1.1.0")
+    });
+
+    let findings = compare_sdist_only(&old, &new);
+
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].kind, FindingKind::SdistOnly);
+    assert_eq!(findings[0].severity, Severity::High);
+    assert_eq!(
+        findings[0].message,
+        "1.1.0 ships no wheels, only requests-1.1.0.tar.gz (1.0.0 had 2 wheel(s))"
+    );
+}
+
+#[test]
+fn test_sdist_only_ignored_when_wheels_remain() {
+    let old = lock_with(LockedPackage {
+        files: wheels(&["requests-1.0.0-py3-none-any.whl"]),
+        ..package("requests", "1.0.0")
+    });
+    let new = lock_with(LockedPackage {
+        files: wheels(&["requests-1.1.0.tar.gz", "requests-1.1.0-py3-none-any.whl"]),
+        ..package("requests", "1.1.0")
+    });
+
+    assert!(compare_sdist_only(&old, &new).is_empty());
+}
+
+#[test]
+fn test_sdist_only_ignored_for_packages_that_never_had_wheels() {
+    let old = lock_with(LockedPackage {
+        files: wheels(&["requests-1.0.0.tar.gz"]),
+        ..package("requests", "1.0.0")
+    });
+    let new = lock_with(LockedPackage {
+        files: wheels(&["requests-1.1.0.tar.gz"]),
+        ..package("requests", "1.1.0")
+    });
+
+    assert!(compare_sdist_only(&old, &new).is_empty());
+}
+
+// Tests for compare_metadata
+
+#[test]
+fn test_detects_metadata_changes() {
+    let old = LockMetadata {
+        generator: Some("Poetry 1.8.3".to_string()),
+        lock_version: Some("2.0".to_string()),
+        python_versions: Some("^3.9".to_string()),
+        content_hash: Some("aaaa".to_string()),
+    };
+    let new = LockMetadata {
+        generator: Some("Poetry 2.0.1".to_string()),
+        lock_version: Some("2.1".to_string()),
+        python_versions: Some("^3.9".to_string()),
+        content_hash: None,
+    };
+
+    let changes = compare_metadata(&old, &new);
+    let fields: Vec<&str> = changes.iter().map(|c| c.field).collect();
+
+    assert_eq!(fields, vec!["generator", "lock-version", "content-hash"]);
+    assert_eq!(changes[0].old_value.as_deref(), Some("Poetry 1.8.3"));
+    assert_eq!(changes[0].new_value.as_deref(), Some("Poetry 2.0.1"));
+    assert!(changes[2].new_value.is_none());
+}
+
+#[test]
+fn test_unchanged_metadata() {
+    let metadata = LockMetadata {
+        lock_version: Some("2.0".to_string()),
+        ..Default::default()
+    };
+
+    assert!(compare_metadata(&metadata, &metadata.clone()).is_empty());
+}
+
+// Tests for next_breaking_version
+
+#[test]
+fn test_next_breaking_version() {
+    let next = |v: &str| next_breaking_version(&v.parse().unwrap()).to_string();
+
+    assert_eq!(next("2.31.0"), "3.0.0");
+    assert_eq!(next("1"), "2.0.0");
+    assert_eq!(next("0.9.2"), "0.10.0");
+    assert_eq!(next("0.0.3"), "0.1.0");
+}
+
+// Tests for compare_constraints
+
+fn pyproject(dependencies: &[(&str, &str, &str)]) -> Pyproject {
+    Pyproject {
+        dependencies: dependencies
+            .iter()
+            .map(|(name, constraint, group)| DeclaredDependency {
+                name: name.to_string(),
+                constraint: constraint.to_string(),
+                group: group.to_string(),
+                ..Default::default()
+            })
+            .collect(),
+    }
+}
+
+#[test]
+fn test_classifies_constraint_changes() {
+    let old = pyproject(&[
+        ("requests", "^2.28", "main"),
+        ("django", ">=4.2,<5", "main"),
+        ("click", "^8.0", "main"),
+        ("numpy", "~1.26", "main"),
+        ("pytest", "^7.0", "dev"),
+    ]);
+    let new = pyproject(&[
+        ("requests", ">=2.28", "main"),
+        ("Django", ">=4.2.5,<5", "main"),
+        ("click", "^9.0", "main"),
+        ("numpy", "~=1.26.0", "main"),
+        ("rich", "^13.0", "main"),
+    ]);
+
+    let changes = compare_constraints(&old, &new);
+    let summary: Vec<(&str, &str, ConstraintChangeKind)> = changes
+        .iter()
+        .map(|c| (c.package_name.as_str(), c.group.as_str(), c.kind))
+        .collect();
+
+    assert_eq!(
+        summary,
+        vec![
+            ("click", "main", ConstraintChangeKind::Changed),
+            ("Django", "main", ConstraintChangeKind::Narrowed),
+            ("pytest", "dev", ConstraintChangeKind::Removed),
+            ("requests", "main", ConstraintChangeKind::Widened),
+            ("rich", "main", ConstraintChangeKind::Added),
+        ]
+    );
+    assert_eq!(changes[2].old_constraint.as_deref(), Some("^7.0"));
+    assert!(changes[2].new_constraint.is_none());
+}
+
+#[test]
+fn test_ignores_equivalent_constraint_rewrites() {
+    let old = pyproject(&[("requests", "^2.28", "main")]);
+    let new = pyproject(&[("requests", ">=2.28,<3.0", "main")]);
+
+    assert!(compare_constraints(&old, &new).is_empty());
+}
+
+// Tests for unexpected_breaking_bumps
+
+fn breaking_bump(name: &str, old_version: &str, new_version: &str) -> VersionBump {
+    VersionBump {
+        package_name: name.to_string(),
+        old_version: old_version.to_string(),
+        new_version: new_version.to_string(),
+        is_breaking: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_flags_breaking_bumps_without_constraint_change() {
+    let old = pyproject(&[("click", "^8.0", "main"), ("django", "*", "main")]);
+    let new = pyproject(&[("click", "^9.0", "main"), ("django", "*", "main")]);
+    let bumps = vec![
+        breaking_bump("click", "8.1.7", "9.0.0"),
+        breaking_bump("django", "4.2.0", "5.0.0"),
+        breaking_bump("sqlparse", "0.4.4", "0.5.0"),
+    ];
+
+    let changes = compare_constraints(&old, &new);
+    let findings = unexpected_breaking_bumps(&bumps, &changes, &new);
+
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].package_name, "django");
+    assert_eq!(findings[0].kind, FindingKind::UnexpectedBreakingBump);
+    assert_eq!(findings[0].severity, Severity::Medium);
+    assert_eq!(
+        findings[0].message,
+        "breaking bump 4.2.0 → 5.0.0 without a constraint change (declared * (main))"
+    );
+}
@@ -0,0 +1,60 @@
+use pdrift_rs::constraint::Constraint;
+use pep440_rs::Version;
+
+fn allows(constraint: &str, version: &str) -> bool {
+    let constraint: Constraint = constraint.parse().unwrap();
+    constraint.contains(&version.parse::<Version>().unwrap())
+}
+
+#[test]
+fn test_pep440_specifiers() {
+    assert!(allows(">=1.21.1,<3", "2.0.0"));
+    assert!(!allows(">=1.21.1,<3", "3.0.0"));
+    assert!(allows(">=2.7, !=3.0.*, !=3.1.*", "3.2"));
+    assert!(!allows(">=2.7, !=3.0.*, !=3.1.*", "3.1.4"));
+    assert!(allows("~=1.4", "1.9"));
+    assert!(!allows("~=1.4", "2.0"));
+}
+
+#[test]
+fn test_caret_constraints() {
+    assert!(allows("^1.2.3", "1.9.0"));
+    assert!(!allows("^1.2.3", "2.0.0"));
+    assert!(!allows("^1.2.3", "1.2.2"));
+    assert!(allows("^0.2.3", "0.2.9"));
+    assert!(!allows("^0.2.3", "0.3.0"));
+    assert!(!allows("^0.0.3", "0.0.4"));
+    assert!(allows("^3.9", "3.12"));
+    assert!(!allows("^3.9", "4.0"));
+}
+
+#[test]
+fn test_tilde_constraints() {
+    assert!(allows("~1.2.3", "1.2.9"));
+    assert!(!allows("~1.2.3", "1.3.0"));
+    assert!(allows("~1", "1.9"));
+    assert!(!allows("~1", "2.0"));
+}
+
+#[test]
+fn test_bare_versions_and_wildcards() {
+    assert!(allows("1.2.3", "1.2.3"));
+    assert!(!allows("1.2.3", "1.2.4"));
+    assert!(allows("1.2.*", "1.2.7"));
+    assert!(!allows("1.2.*", "1.3.0"));
+    assert!(allows("*", "99.0"));
+    assert!(allows("", "1.0"));
+}
+
+#[test]
+fn test_union_constraints() {
+    assert!(allows(">=2.7,<2.8 || >=3.6", "2.7.18"));
+    assert!(allows(">=2.7,<2.8 || >=3.6", "3.12"));
+    assert!(!allows(">=2.7,<2.8 || >=3.6", "3.5"));
+}
+
+#[test]
+fn test_space_separated_terms() {
+    assert!(allows(">= 3.8 < 4.0", "3.9"));
+    assert!(!allows(">= 3.8 < 4.0", "4.0"));
+}
@@ -0,0 +1,83 @@
+use pdrift_rs::footprint::{format_size, format_size_delta, Footprint};
+use pdrift_rs::lockfile::{Artifact, LockedPackage};
+use std::collections::HashMap;
+
+fn package(name: &str, sizes: &[Option<u64>]) -> (String, LockedPackage) {
+    (
+        name.to_string(),
+        LockedPackage {
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+            files: sizes
+                .iter()
+                .enumerate()
+                .map(|(i, size)| Artifact {
+                    file: format!("{}-{}.whl", name, i),
+                    size: *size,
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+fn test_footprint_totals_and_changes() {
+    let old = HashMap::from([
+        package("torch", &[Some(700), Some(100)]),
+        package("six", &[Some(10)]),
+        package("legacy", &[Some(50)]),
+    ]);
+    let new = HashMap::from([
+        package("torch", &[Some(1500), Some(100)]),
+        package("six", &[Some(10)]),
+        package("rich", &[Some(200), None]),
+    ]);
+
+    let footprint = Footprint::new(&old, &new);
+
+    assert_eq!(footprint.old_packages, 3);
+    assert_eq!(footprint.new_packages, 3);
+    assert_eq!(footprint.old_artifacts, 4);
+    assert_eq!(footprint.new_artifacts, 5);
+    assert_eq!(footprint.old_size, Some(860));
+    assert_eq!(footprint.new_size, Some(1810));
+
+    let changes: Vec<(&str, Option<u64>, Option<u64>, i64)> = footprint
+        .size_changes
+        .iter()
+        .map(|c| (c.package_name.as_str(), c.old_size, c.new_size, c.delta()))
+        .collect();
+    assert_eq!(
+        changes,
+        vec![
+            ("torch", Some(800), Some(1600), 800),
+            ("rich", None, Some(200), 200),
+            ("legacy", Some(50), None, -50),
+        ]
+    );
+}
+
+#[test]
+fn test_footprint_without_sizes() {
+    let old = HashMap::from([package("six", &[None, None])]);
+    let new = HashMap::from([package("six", &[None])]);
+
+    let footprint = Footprint::new(&old, &new);
+
+    assert_eq!(footprint.old_artifacts, 2);
+    assert_eq!(footprint.new_artifacts, 1);
+    assert!(footprint.old_size.is_none());
+    assert!(footprint.size_changes.is_empty());
+}
+
+#[test]
+fn test_format_size() {
+    assert_eq!(format_size(512), "512 B");
+    assert_eq!(format_size(1536), "1.5 KiB");
+    assert_eq!(format_size(5 * 1024 * 1024), "5.0 MiB");
+    assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3.0 GiB");
+    assert_eq!(format_size_delta(2048), "+2.0 KiB");
+    assert_eq!(format_size_delta(-200), "-200 B");
+}
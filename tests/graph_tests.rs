@@ -0,0 +1,178 @@
+use pdrift_rs::graph::{DependencyGraph, MAX_PATHS};
+use pdrift_rs::lockfile::{Dependency, LockedPackage};
+use std::collections::{HashMap, HashSet};
+
+fn packages(edges: &[(&str, &[&str])]) -> HashMap<String, LockedPackage> {
+    edges
+        .iter()
+        .map(|(name, dependencies)| {
+            (
+                name.to_string(),
+                LockedPackage {
+                    name: name.to_string(),
+                    version: "1.0.0".to_string(),
+                    dependencies: dependencies
+                        .iter()
+                        .map(|d| Dependency {
+                            name: d.to_string(),
+                            constraint: "*".to_string(),
+                            ..Default::default()
+                        })
+                        .collect(),
+                    ..Default::default()
+                },
+            )
+        })
+        .collect()
+}
+
+fn set(names: &[&str]) -> HashSet<String> {
+    names.iter().map(|n| n.to_string()).collect()
+}
+
+#[test]
+fn test_roots_have_no_dependents() {
+    let graph = DependencyGraph::new(&packages(&[
+        ("app", &["requests"]),
+        ("requests", &["urllib3"]),
+        ("urllib3", &[]),
+    ]));
+
+    assert_eq!(graph.roots(), set(&["app"]));
+}
+
+#[test]
+fn test_shortest_path_to_transitive_dependency() {
+    let graph = DependencyGraph::new(&packages(&[
+        ("app", &["requests"]),
+        ("requests", &["urllib3"]),
+        ("urllib3", &[]),
+    ]));
+
+    let paths = graph.shortest_paths("urllib3", &set(&["app"]));
+
+    assert_eq!(paths, vec![vec!["app", "requests", "urllib3"]]);
+}
+
+#[test]
+fn test_shortest_path_prefers_shorter_chain() {
+    let graph = DependencyGraph::new(&packages(&[
+        ("app", &["requests", "urllib3"]),
+        ("requests", &["urllib3"]),
+        ("urllib3", &[]),
+    ]));
+
+    let paths = graph.shortest_paths("urllib3", &set(&["app"]));
+
+    assert_eq!(paths, vec![vec!["app", "urllib3"]]);
+}
+
+#[test]
+fn test_shortest_paths_returns_all_equal_length_chains() {
+    let graph = DependencyGraph::new(&packages(&[
+        ("botocore", &["urllib3"]),
+        ("requests", &["urllib3"]),
+        ("urllib3", &[]),
+    ]));
+
+    let paths = graph.shortest_paths("urllib3", &set(&["botocore", "requests"]));
+
+    assert_eq!(
+        paths,
+        vec![vec!["botocore", "urllib3"], vec!["requests", "urllib3"]]
+    );
+}
+
+#[test]
+fn test_shortest_paths_are_capped() {
+    let graph = DependencyGraph::new(&packages(&[
+        ("a", &["shared"]),
+        ("b", &["shared"]),
+        ("c", &["shared"]),
+        ("d", &["shared"]),
+        ("shared", &[]),
+    ]));
+
+    let paths = graph.shortest_paths("shared", &set(&["a", "b", "c", "d"]));
+
+    assert_eq!(paths.len(), MAX_PATHS);
+}
+
+#[test]
+fn test_root_path_is_itself() {
+    let graph = DependencyGraph::new(&packages(&[("app", &[])]));
+
+    let paths = graph.shortest_paths("app", &set(&["app"]));
+
+    assert_eq!(paths, vec![vec!["app"]]);
+}
+
+#[test]
+fn test_unreachable_package_has_no_paths() {
+    let graph = DependencyGraph::new(&packages(&[("app", &[]), ("orphan", &[])]));
+
+    assert!(graph.shortest_paths("orphan", &set(&["app"])).is_empty());
+    assert!(graph.shortest_paths("missing", &set(&["app"])).is_empty());
+}
+
+#[test]
+fn test_ignores_dependencies_missing_from_lock() {
+    let graph = DependencyGraph::new(&packages(&[("app", &["not-locked"])]));
+
+    assert_eq!(graph.roots(), set(&["app"]));
+}
+
+#[test]
+fn test_direct_and_all_dependents() {
+    let graph = DependencyGraph::new(&packages(&[
+        ("app", &["grpcio", "googleapis"]),
+        ("googleapis", &["grpcio", "protobuf"]),
+        ("grpcio", &["protobuf"]),
+        ("protobuf", &[]),
+    ]));
+
+    assert_eq!(
+        graph.direct_dependents("protobuf"),
+        vec!["googleapis", "grpcio"]
+    );
+    assert_eq!(
+        graph.all_dependents("protobuf"),
+        vec!["app", "googleapis", "grpcio"]
+    );
+    assert!(graph.all_dependents("app").is_empty());
+}
+
+#[test]
+fn test_all_dependents_handles_cycles() {
+    let graph = DependencyGraph::new(&packages(&[("a", &["b"]), ("b", &["a"])]));
+
+    assert_eq!(graph.all_dependents("a"), vec!["b"]);
+}
+
+#[test]
+fn test_cycles() {
+    let graph = DependencyGraph::new(&packages(&[
+        ("app", &["sphinx"]),
+        ("sphinx", &["sphinxcontrib", "docutils"]),
+        ("sphinxcontrib", &["sphinx"]),
+        ("docutils", &[]),
+        ("c", &["d"]),
+        ("d", &["e"]),
+        ("e", &["c", "d"]),
+    ]));
+
+    assert_eq!(
+        graph.cycles(),
+        vec![
+            vec!["c", "d", "e", "c"],
+            vec!["sphinx", "sphinxcontrib", "sphinx"],
+        ]
+    );
+}
+
+#[test]
+fn test_no_cycles_in_acyclic_graph() {
+    let graph = DependencyGraph::new(&packages(&[("a", &["b", "c"]), ("b", &["c"]), ("c", &[])]));
+
+    assert!(graph.cycles().is_empty());
+}
@@ -0,0 +1,99 @@
+use pdrift_rs::lint::lint_constraints;
+use pdrift_rs::lockfile::LockedPackage;
+use pdrift_rs::pyproject::{DeclaredDependency, Pyproject};
+use std::collections::HashMap;
+
+fn declared(name: &str, constraint: &str) -> DeclaredDependency {
+    DeclaredDependency {
+        name: name.to_string(),
+        constraint: constraint.to_string(),
+        group: "main".to_string(),
+        ..Default::default()
+    }
+}
+
+fn locked(entries: &[(&str, &str)]) -> HashMap<String, LockedPackage> {
+    entries
+        .iter()
+        .map(|(name, version)| {
+            (
+                name.to_string(),
+                LockedPackage {
+                    name: name.to_string(),
+                    version: version.to_string(),
+                    ..Default::default()
+                },
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_lints_unbounded_constraints() {
+    let pyproject = Pyproject {
+        dependencies: vec![
+            declared("requests", ">=2.0"),
+            declared("rich", "*"),
+            declared("fastapi", ">=0.100,<1"),
+            declared("numpy", "^1.26"),
+            declared("pydantic", ">=2,<3"),
+        ],
+    };
+    let packages = locked(&[
+        ("requests", "2.31.0"),
+        ("rich", "13.7.0"),
+        ("fastapi", "0.110.0"),
+        ("numpy", "1.26.4"),
+        ("pydantic", "2.6.0"),
+    ]);
+
+    let issues = lint_constraints(&pyproject, &packages);
+    let summary: Vec<(&str, &str, &str, &str)> = issues
+        .iter()
+        .map(|i| {
+            (
+                i.package_name.as_str(),
+                i.allowed_version.as_str(),
+                i.suggestion.as_str(),
+                i.pep440_suggestion.as_str(),
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        summary,
+        vec![
+            ("fastapi", "0.111.0", "^0.110.0", ">=0.110.0,<0.111.0"),
+            ("requests", "3.0.0", "^2.31.0", ">=2.31.0,<3.0.0"),
+            ("rich", "14.0.0", "^13.7.0", ">=13.7.0,<14.0.0"),
+        ]
+    );
+}
+
+#[test]
+fn test_lint_skips_unlocked_dependencies() {
+    let pyproject = Pyproject {
+        dependencies: vec![declared("requests", ">=2.0")],
+    };
+
+    assert!(lint_constraints(&pyproject, &HashMap::new()).is_empty());
+}
+
+#[test]
+fn test_lint_skips_direct_references() {
+    let pyproject = Pyproject {
+        dependencies: vec![
+            DeclaredDependency {
+                direct_reference: true,
+                ..declared("mylib", "*")
+            },
+            DeclaredDependency {
+                direct_reference: true,
+                ..declared("locallib", "*")
+            },
+        ],
+    };
+    let packages = locked(&[("mylib", "1.2.0"), ("locallib", "0.3.0")]);
+
+    assert!(lint_constraints(&pyproject, &packages).is_empty());
+}
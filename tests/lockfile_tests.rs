@@ -1,4 +1,7 @@
-use pdrift_rs::lockfile::{normalize_name, parse_lockfile};
+use pdrift_rs::lockfile::{
+    filter_by_groups, load_lockfile, normalize_name, parse_lockfile, Artifact, LockedPackage,
+};
+use std::collections::HashMap;
 use std::path::Path;
 use tempfile::TempDir;
 
@@ -152,3 +155,485 @@ name = "mypackage"
     let result = parse_lockfile(&lock_file);
     assert!(result.is_err());
 }
+
+#[test]
+fn test_parse_groups() {
+    let tmp_dir = TempDir::new().unwrap();
+    let lock_file = tmp_dir.path().join("poetry.lock");
+
+    let lock_content = r#"[[package]]
+name = "pytest"
+version = "8.0.0"
+groups = ["dev", "test"]
+
+[[package]]
+name = "requests"
+version = "2.31.0"
+groups = ["main"]
+"#;
+
+    std::fs::write(&lock_file, lock_content).unwrap();
+
+    let result = parse_lockfile(&lock_file).unwrap();
+
+    assert_eq!(result["pytest"].groups, vec!["dev", "test"]);
+    assert_eq!(result["requests"].groups, vec!["main"]);
+}
+
+#[test]
+fn test_parse_legacy_category() {
+    let tmp_dir = TempDir::new().unwrap();
+    let lock_file = tmp_dir.path().join("poetry.lock");
+
+    let lock_content = r#"[[package]]
+name = "pytest"
+version = "7.4.0"
+category = "dev"
+
+[[package]]
+name = "requests"
+version = "2.31.0"
+"#;
+
+    std::fs::write(&lock_file, lock_content).unwrap();
+
+    let result = parse_lockfile(&lock_file).unwrap();
+
+    assert_eq!(result["pytest"].groups, vec!["dev"]);
+    assert!(result["requests"].groups.is_empty());
+}
+
+#[test]
+fn test_parse_dependencies() {
+    let tmp_dir = TempDir::new().unwrap();
+    let lock_file = tmp_dir.path().join("poetry.lock");
+
+    let lock_content = r#"[[package]]
+name = "requests"
+version = "2.31.0"
+
+[package.dependencies]
+certifi = ">=2017.4.17"
+PySocks = {version = ">=1.5.6,<1.5.7 || >1.5.7", optional = true}
+urllib3 = {version = ">=1.21.1,<3", extras = ["socks"], markers = "python_version >= \"3.8\""}
+numpy = [
+    {version = ">=1.23", markers = "python_version < \"3.12\""},
+    {version = ">=1.26", markers = "python_version >= \"3.12\""},
+]
+"#;
+
+    std::fs::write(&lock_file, lock_content).unwrap();
+
+    let result = parse_lockfile(&lock_file).unwrap();
+    let deps = &result["requests"].dependencies;
+
+    assert_eq!(deps.len(), 5);
+
+    let certifi = deps.iter().find(|d| d.name == "certifi").unwrap();
+    assert_eq!(certifi.constraint, ">=2017.4.17");
+    assert!(!certifi.optional);
+    assert!(certifi.markers.is_none());
+
+    let pysocks = deps.iter().find(|d| d.name == "PySocks").unwrap();
+    assert!(pysocks.optional);
+
+    let urllib3 = deps.iter().find(|d| d.name == "urllib3").unwrap();
+    assert_eq!(urllib3.constraint, ">=1.21.1,<3");
+    assert_eq!(urllib3.extras, vec!["socks"]);
+    assert_eq!(
+        urllib3.markers.as_deref(),
+        Some("python_version >= \"3.8\"")
+    );
+
+    assert_eq!(deps.iter().filter(|d| d.name == "numpy").count(), 2);
+}
+
+#[test]
+fn test_parse_source() {
+    let tmp_dir = TempDir::new().unwrap();
+    let lock_file = tmp_dir.path().join("poetry.lock");
+
+    let lock_content = r#"[[package]]
+name = "mylib"
+version = "1.0.0"
+
+[package.source]
+type = "git"
+url = "https://github.com/example/mylib.git"
+reference = "main"
+resolved_reference = "0123abcd"
+
+[[package]]
+name = "requests"
+version = "2.31.0"
+"#;
+
+    std::fs::write(&lock_file, lock_content).unwrap();
+
+    let result = parse_lockfile(&lock_file).unwrap();
+
+    let source = result["mylib"].source.as_ref().unwrap();
+    assert_eq!(source.source_type, "git");
+    assert_eq!(source.url, "https://github.com/example/mylib.git");
+    assert_eq!(source.reference.as_deref(), Some("main"));
+    assert_eq!(source.resolved_reference.as_deref(), Some("0123abcd"));
+    assert!(result["requests"].source.is_none());
+}
+
+#[test]
+fn test_parse_optional_and_extras() {
+    let tmp_dir = TempDir::new().unwrap();
+    let lock_file = tmp_dir.path().join("poetry.lock");
+
+    let lock_content = r#"[[package]]
+name = "requests"
+version = "2.31.0"
+optional = false
+
+[package.extras]
+socks = ["PySocks (>=1.5.6,!=1.5.7)"]
+use-chardet-on-py3 = ["chardet (>=3.0.2,<6)"]
+
+[[package]]
+name = "pysocks"
+version = "1.7.1"
+optional = true
+"#;
+
+    std::fs::write(&lock_file, lock_content).unwrap();
+
+    let result = parse_lockfile(&lock_file).unwrap();
+
+    assert!(!result["requests"].optional);
+    assert!(result["pysocks"].optional);
+    assert_eq!(result["requests"].extras.len(), 2);
+    assert_eq!(
+        result["requests"].extras["socks"],
+        vec!["PySocks (>=1.5.6,!=1.5.7)"]
+    );
+    assert!(result["pysocks"].extras.is_empty());
+}
+
+#[test]
+fn test_wheel_tags() {
+    let wheel = |file: &str| Artifact {
+        file: file.to_string(),
+        ..Default::default()
+    };
+
+    let tags = wheel("numpy-2.0.0-cp312-cp312-manylinux_2_17_x86_64.manylinux2014_x86_64.whl")
+        .wheel_tags()
+        .unwrap();
+    assert_eq!(tags.python, vec!["cp312"]);
+    assert_eq!(tags.abi, vec!["cp312"]);
+    assert_eq!(
+        tags.platform,
+        vec!["manylinux_2_17_x86_64", "manylinux2014_x86_64"]
+    );
+
+    let tags = wheel("six-1.16.0-1-py2.py3-none-any.whl")
+        .wheel_tags()
+        .unwrap();
+    assert_eq!(tags.python, vec!["py2", "py3"]);
+    assert_eq!(tags.platform, vec!["any"]);
+
+    assert!(wheel("six-1.16.0.tar.gz").wheel_tags().is_none());
+    assert!(wheel("broken.whl").wheel_tags().is_none());
+}
+
+#[test]
+fn test_parse_files() {
+    let tmp_dir = TempDir::new().unwrap();
+    let lock_file = tmp_dir.path().join("poetry.lock");
+
+    let lock_content = r#"[[package]]
+name = "requests"
+version = "2.31.0"
+files = [
+    {file = "requests-2.31.0-py3-none-any.whl", hash = "sha256:aaaa"},
+    {file = "requests-2.31.0.tar.gz", hash = "sha256:bbbb"},
+]
+"#;
+
+    std::fs::write(&lock_file, lock_content).unwrap();
+
+    let result = parse_lockfile(&lock_file).unwrap();
+    let files = &result["requests"].files;
+
+    assert_eq!(files.len(), 2);
+    assert_eq!(files[0].file, "requests-2.31.0-py3-none-any.whl");
+    assert_eq!(files[0].hash, "sha256:aaaa");
+}
+
+#[test]
+fn test_parse_uv_and_pylock_artifacts() {
+    let tmp_dir = TempDir::new().unwrap();
+    let uv_lock = tmp_dir.path().join("uv.lock");
+    let pylock = tmp_dir.path().join("pylock.toml");
+
+    std::fs::write(
+        &uv_lock,
+        r#"[[package]]
+name = "six"
+version = "1.16.0"
+sdist = { url = "https://files.example/six-1.16.0.tar.gz", hash = "sha256:aaaa", size = 34041 }
+wheels = [
+    { url = "https://files.example/six-1.16.0-py2.py3-none-any.whl", hash = "sha256:bbbb", size = 11053 },
+]
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+        &pylock,
+        r#"[[packages]]
+name = "six"
+version = "1.16.0"
+wheels = [
+    { name = "six-1.16.0-py2.py3-none-any.whl", size = 11053, hashes = { sha256 = "bbbb" } },
+]
+"#,
+    )
+    .unwrap();
+
+    let files = &parse_lockfile(&uv_lock).unwrap()["six"].files;
+    assert_eq!(files.len(), 2);
+    assert_eq!(files[0].file, "six-1.16.0.tar.gz");
+    assert_eq!(files[0].hash, "sha256:aaaa");
+    assert_eq!(files[0].size, Some(34041));
+    assert_eq!(files[1].file, "six-1.16.0-py2.py3-none-any.whl");
+
+    let files = &parse_lockfile(&pylock).unwrap()["six"].files;
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].file, "six-1.16.0-py2.py3-none-any.whl");
+    assert_eq!(files[0].hash, "sha256:bbbb");
+    assert_eq!(files[0].size, Some(11053));
+}
+
+#[test]
+fn test_parse_legacy_metadata_files() {
+    let tmp_dir = TempDir::new().unwrap();
+    let lock_file = tmp_dir.path().join("poetry.lock");
+
+    let lock_content = r#"[[package]]
+name = "Django-CORS-Headers"
+version = "4.0.0"
+
+[metadata]
+lock-version = "1.1"
+
+[metadata.files]
+django-cors-headers = [
+    {file = "django_cors_headers-4.0.0-py3-none-any.whl", hash = "sha256:cccc"},
+]
+"#;
+
+    std::fs::write(&lock_file, lock_content).unwrap();
+
+    let result = parse_lockfile(&lock_file).unwrap();
+    let files = &result["django_cors_headers"].files;
+
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].hash, "sha256:cccc");
+}
+
+#[test]
+fn test_parse_python_versions() {
+    let tmp_dir = TempDir::new().unwrap();
+    let lock_file = tmp_dir.path().join("poetry.lock");
+
+    let lock_content = r#"[[package]]
+name = "numpy"
+version = "2.0.0"
+python-versions = ">=3.9"
+
+[[package]]
+name = "six"
+version = "1.16.0"
+
+[metadata]
+lock-version = "2.0"
+python-versions = "^3.9"
+"#;
+
+    std::fs::write(&lock_file, lock_content).unwrap();
+
+    let lockfile = load_lockfile(&lock_file).unwrap();
+
+    assert_eq!(
+        lockfile.packages["numpy"].python_versions.as_deref(),
+        Some(">=3.9")
+    );
+    assert!(lockfile.packages["six"].python_versions.is_none());
+    assert_eq!(lockfile.metadata.python_versions.as_deref(), Some("^3.9"));
+}
+
+#[test]
+fn test_parse_lock_metadata() {
+    let tmp_dir = TempDir::new().unwrap();
+    let poetry_lock = tmp_dir.path().join("poetry.lock");
+    let uv_lock = tmp_dir.path().join("uv.lock");
+
+    std::fs::write(
+        &poetry_lock,
+        r#"# This file is automatically @generated by Poetry 1.8.3 and should not be changed by hand.
+
+[[package]]
+name = "six"
+version = "1.16.0"
+
+[metadata]
+lock-version = "2.0"
+python-versions = "^3.9"
+content-hash = "abc123"
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+        &uv_lock,
+        r#"version = 1
+requires-python = ">=3.10"
+
+[[package]]
+name = "six"
+version = "1.16.0"
+"#,
+    )
+    .unwrap();
+
+    let metadata = load_lockfile(&poetry_lock).unwrap().metadata;
+    assert_eq!(metadata.generator.as_deref(), Some("Poetry 1.8.3"));
+    assert_eq!(metadata.lock_version.as_deref(), Some("2.0"));
+    assert_eq!(metadata.python_versions.as_deref(), Some("^3.9"));
+    assert_eq!(metadata.content_hash.as_deref(), Some("abc123"));
+
+    let metadata = load_lockfile(&uv_lock).unwrap().metadata;
+    assert!(metadata.generator.is_none());
+    assert_eq!(metadata.lock_version.as_deref(), Some("1"));
+    assert_eq!(metadata.python_versions.as_deref(), Some(">=3.10"));
+    assert!(metadata.content_hash.is_none());
+}
+
+fn grouped_packages() -> HashMap<String, LockedPackage> {
+    let mut packages = HashMap::new();
+    for (name, groups) in [
+        ("requests", vec!["main"]),
+        ("pytest", vec!["dev"]),
+        ("coverage", vec!["dev", "main"]),
+    ] {
+        packages.insert(
+            name.to_string(),
+            LockedPackage {
+                name: name.to_string(),
+                version: "1.0.0".to_string(),
+                groups: groups.into_iter().map(String::from).collect(),
+                ..Default::default()
+            },
+        );
+    }
+    packages
+}
+
+#[test]
+fn test_filter_by_groups_include() {
+    let result = filter_by_groups(grouped_packages(), &["main".to_string()], &[]).unwrap();
+
+    assert_eq!(result.len(), 2);
+    assert!(result.contains_key("requests"));
+    assert!(result.contains_key("coverage"));
+}
+
+#[test]
+fn test_filter_by_groups_exclude() {
+    let result = filter_by_groups(grouped_packages(), &[], &["dev".to_string()]).unwrap();
+
+    assert_eq!(result.len(), 2);
+    assert!(result.contains_key("requests"));
+    assert!(result.contains_key("coverage"));
+    assert!(!result.contains_key("pytest"));
+}
+
+#[test]
+fn test_filter_by_groups_exclude_keeps_packages_without_groups() {
+    let mut packages = grouped_packages();
+    packages.insert(
+        "legacy".to_string(),
+        LockedPackage {
+            name: "legacy".to_string(),
+            version: "1.0.0".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let result = filter_by_groups(packages.clone(), &[], &["dev".to_string()]).unwrap();
+    assert!(result.contains_key("legacy"));
+    assert!(!result.contains_key("pytest"));
+
+    let result = filter_by_groups(packages, &["main".to_string()], &[]).unwrap();
+    assert!(!result.contains_key("legacy"));
+}
+
+#[test]
+fn test_filter_by_groups_no_filters() {
+    let result = filter_by_groups(grouped_packages(), &[], &[]).unwrap();
+
+    assert_eq!(result.len(), 3);
+}
+
+#[test]
+fn test_filter_by_groups_without_group_data() {
+    let tmp_dir = TempDir::new().unwrap();
+    let lock_file = tmp_dir.path().join("poetry.lock");
+    std::fs::write(
+        &lock_file,
+        r#"[[package]]
+name = "pytest"
+version = "8.0.0"
+
+[[package]]
+name = "requests"
+version = "2.31.0"
+
+[metadata]
+lock-version = "2.0"
+"#,
+    )
+    .unwrap();
+    let packages = parse_lockfile(&lock_file).unwrap();
+
+    assert!(packages["pytest"].groups.is_empty());
+    assert!(filter_by_groups(packages.clone(), &[], &["dev".to_string()]).is_err());
+    assert!(filter_by_groups(packages.clone(), &["main".to_string()], &[]).is_err());
+    assert_eq!(filter_by_groups(packages, &[], &[]).unwrap().len(), 2);
+}
+
+#[test]
+fn test_records_package_lines() {
+    let tmp_dir = TempDir::new().unwrap();
+    let lock_path = tmp_dir.path().join("poetry.lock");
+    std::fs::write(
+        &lock_path,
+        r#"# This file is automatically @generated by Poetry 1.8.3 and should not be changed by hand.
+
+[[package]]
+name = "Django"
+version = "5.0.0"
+
+[package.dependencies]
+sqlparse = ">=0.3.1"
+
+[[package]]
+version = "0.5.0"
+name = "sqlparse"
+
+[metadata]
+lock-version = "2.0"
+"#,
+    )
+    .unwrap();
+
+    let lockfile = load_lockfile(&lock_path).unwrap();
+
+    assert_eq!(lockfile.package_lines.len(), 2);
+    assert_eq!(lockfile.package_lines["django"], 4);
+    assert_eq!(lockfile.package_lines["sqlparse"], 12);
+}
@@ -0,0 +1,191 @@
+use pdrift_rs::lockfile::{parse_lockfile, Dependency, LockedPackage};
+use pdrift_rs::markers::{evaluate, filter_by_environment, Environment, Platform};
+use std::collections::HashMap;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn environment(python: &str, platform: Platform) -> Environment {
+    Environment {
+        python_version: Some(python.parse().unwrap()),
+        platform: Some(platform),
+    }
+}
+
+#[test]
+fn test_evaluate_python_version() {
+    let env = environment("3.12", Platform::Linux);
+
+    assert!(evaluate(r#"python_version >= "3.8""#, &env));
+    assert!(!evaluate(r#"python_version < "3.11""#, &env));
+    assert!(evaluate(r#"python_full_version >= "3.12.0""#, &env));
+    assert!(!evaluate(r#"python_version == "3.9""#, &env));
+}
+
+#[test]
+fn test_evaluate_platform() {
+    let linux = environment("3.12", Platform::Linux);
+    let windows = environment("3.12", Platform::Windows);
+
+    assert!(!evaluate(r#"sys_platform == "win32""#, &linux));
+    assert!(evaluate(r#"sys_platform == "win32""#, &windows));
+    assert!(evaluate(r#"platform_system != "Windows""#, &linux));
+    assert!(evaluate(r#"os_name == 'nt'"#, &windows));
+    assert!(evaluate(r#""linux" in sys_platform"#, &linux));
+    assert!(evaluate(r#"sys_platform not in "win32 cygwin""#, &linux));
+}
+
+#[test]
+fn test_evaluate_boolean_operators() {
+    let env = environment("3.10", Platform::Macos);
+
+    assert!(evaluate(
+        r#"python_version < "3.11" and sys_platform == "darwin""#,
+        &env
+    ));
+    assert!(!evaluate(
+        r#"python_version < "3.10" or sys_platform == "win32""#,
+        &env
+    ));
+    assert!(evaluate(
+        r#"(python_version < "3.10" or sys_platform == "darwin") and os_name == "posix""#,
+        &env
+    ));
+}
+
+#[test]
+fn test_evaluate_unknown_or_unset_variables() {
+    let python_only = Environment {
+        python_version: Some("3.12".parse().unwrap()),
+        platform: None,
+    };
+
+    assert!(evaluate(r#"sys_platform == "win32""#, &python_only));
+    assert!(evaluate(r#"extra == "socks""#, &python_only));
+    assert!(evaluate(r#"platform_machine == "arm64""#, &python_only));
+    assert!(evaluate("not a valid marker (", &python_only));
+}
+
+fn dependency(name: &str, markers: Option<&str>) -> Dependency {
+    Dependency {
+        name: name.to_string(),
+        constraint: "*".to_string(),
+        markers: markers.map(String::from),
+        ..Default::default()
+    }
+}
+
+fn package(
+    name: &str,
+    markers: Option<&str>,
+    dependencies: Vec<Dependency>,
+) -> (String, LockedPackage) {
+    (
+        name.to_string(),
+        LockedPackage {
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+            markers: markers.map(String::from),
+            dependencies,
+            ..Default::default()
+        },
+    )
+}
+
+fn marker_packages() -> HashMap<String, LockedPackage> {
+    HashMap::from([
+        package(
+            "click",
+            None,
+            vec![dependency(
+                "colorama",
+                Some(r#"platform_system == "Windows""#),
+            )],
+        ),
+        package("colorama", None, vec![]),
+        package(
+            "tomli",
+            Some(r#"python_version < "3.11""#),
+            vec![dependency("typing_extensions", None)],
+        ),
+        package("typing_extensions", None, vec![]),
+    ])
+}
+
+fn sorted_names(packages: &HashMap<String, LockedPackage>) -> Vec<&str> {
+    let mut names: Vec<&str> = packages.keys().map(|k| k.as_str()).collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn test_filter_by_environment_linux_py312() {
+    let packages = filter_by_environment(marker_packages(), &environment("3.12", Platform::Linux));
+
+    assert_eq!(sorted_names(&packages), vec!["click"]);
+}
+
+#[test]
+fn test_filter_by_environment_windows_py310() {
+    let packages =
+        filter_by_environment(marker_packages(), &environment("3.10", Platform::Windows));
+
+    assert_eq!(
+        sorted_names(&packages),
+        vec!["click", "colorama", "tomli", "typing_extensions"]
+    );
+}
+
+#[test]
+fn test_filter_by_environment_unconstrained() {
+    let packages = filter_by_environment(marker_packages(), &Environment::default());
+
+    assert_eq!(packages.len(), 4);
+}
+
+#[test]
+fn test_parse_package_markers() {
+    let tmp_dir = TempDir::new().unwrap();
+    let lock_file = tmp_dir.path().join("poetry.lock");
+
+    let lock_content = r#"[[package]]
+name = "colorama"
+version = "0.4.6"
+markers = "sys_platform == \"win32\""
+
+[[package]]
+name = "tomli"
+version = "2.0.1"
+markers = {main = "python_version < \"3.11\"", dev = "python_full_version <= \"3.11.0a6\""}
+"#;
+
+    std::fs::write(&lock_file, lock_content).unwrap();
+
+    let result = parse_lockfile(Path::new(&lock_file)).unwrap();
+
+    assert_eq!(
+        result["colorama"].markers.as_deref(),
+        Some(r#"sys_platform == "win32""#)
+    );
+    assert_eq!(
+        result["tomli"].markers.as_deref(),
+        Some(r#"(python_full_version <= "3.11.0a6") or (python_version < "3.11")"#)
+    );
+}
+
+#[test]
+fn test_parse_environment() {
+    let env: Environment = "3.12:linux".parse().unwrap();
+    assert_eq!(env, environment("3.12", Platform::Linux));
+    assert_eq!(env.to_string(), "3.12:linux");
+
+    let env: Environment = "darwin".parse().unwrap();
+    assert_eq!(env.platform, Some(Platform::Macos));
+    assert!(env.python_version.is_none());
+    assert_eq!(env.to_string(), "macos");
+
+    let env: Environment = "3.10".parse().unwrap();
+    assert_eq!(env.to_string(), "3.10");
+
+    assert!("".parse::<Environment>().is_err());
+    assert!("3.12:solaris".parse::<Environment>().is_err());
+}
@@ -1,5 +1,23 @@
-use pdrift_rs::compare::VersionBump;
-use pdrift_rs::output::{format_json, format_text};
+use pdrift_rs::check::{CheckResult, DeclarationMismatch};
+use pdrift_rs::compare::{ConstraintChange, ConstraintChangeKind, MetadataChange, VersionBump};
+use pdrift_rs::findings::{Finding, FindingKind, Severity};
+use pdrift_rs::footprint::{Footprint, SizeChange};
+use pdrift_rs::lint::LintIssue;
+use pdrift_rs::lockfile::{LockLocation, LockedPackage};
+use pdrift_rs::output::{
+    format_check_json, format_check_text, format_json, format_junit, format_lint_json,
+    format_lint_text, format_markdown, format_sarif, format_text, format_validate_json,
+    format_validate_text,
+};
+use pdrift_rs::report::{EnvironmentBumps, Report};
+use pdrift_rs::validate::{UnmetRequirement, ValidationResult};
+
+fn report_with(bumps: Vec<VersionBump>) -> Report {
+    Report {
+        bumps,
+        ..Default::default()
+    }
+}
 
 // Tests for format_text
 
@@ -10,9 +28,10 @@ fn test_formats_breaking_change() {
         old_version: "1.0.0".to_string(),
         new_version: "2.0.0".to_string(),
         is_breaking: true,
+        ..Default::default()
     }];
 
-    let result = format_text(&bumps, false);
+    let result = format_text(&report_with(bumps), false);
 
     assert!(result.contains("pkg"));
     assert!(result.contains("1.0.0"));
@@ -27,16 +46,18 @@ fn test_formats_multiple_breaking_changes() {
             old_version: "1.0.0".to_string(),
             new_version: "2.0.0".to_string(),
             is_breaking: true,
+            ..Default::default()
         },
         VersionBump {
             package_name: "pkg2".to_string(),
             old_version: "0.5.0".to_string(),
             new_version: "0.6.0".to_string(),
             is_breaking: true,
+            ..Default::default()
         },
     ];
 
-    let result = format_text(&bumps, false);
+    let result = format_text(&report_with(bumps), false);
 
     assert!(result.contains("pkg1"));
     assert!(result.contains("pkg2"));
@@ -51,16 +72,18 @@ fn test_shows_count() {
             old_version: "1.0.0".to_string(),
             new_version: "2.0.0".to_string(),
             is_breaking: true,
+            ..Default::default()
         },
         VersionBump {
             package_name: "pkg2".to_string(),
             old_version: "2.0.0".to_string(),
             new_version: "3.0.0".to_string(),
             is_breaking: true,
+            ..Default::default()
         },
     ];
 
-    let result = format_text(&bumps, false);
+    let result = format_text(&report_with(bumps), false);
 
     assert!(result.contains("2 breaking"));
 }
@@ -69,11 +92,145 @@ fn test_shows_count() {
 fn test_empty_when_no_breaking_changes() {
     let bumps: Vec<VersionBump> = vec![];
 
-    let result = format_text(&bumps, false);
+    let result = format_text(&report_with(bumps), false);
 
     assert!(result.to_lowercase().contains("no breaking") || result.trim().is_empty());
 }
 
+#[test]
+fn test_all_flag_includes_added_and_removed_packages() {
+    let report = Report {
+        added: vec![LockedPackage {
+            name: "newpkg".to_string(),
+            version: "1.0.0".to_string(),
+            ..Default::default()
+        }],
+        removed: vec![LockedPackage {
+            name: "oldpkg".to_string(),
+            version: "0.1.0".to_string(),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    let result = format_text(&report, true);
+
+    assert!(result.contains("Added packages:"));
+    assert!(result.contains("newpkg: 1.0.0"));
+    assert!(result.contains("Removed packages:"));
+    assert!(result.contains("oldpkg: 0.1.0"));
+
+    let result = format_text(&report, false);
+
+    assert!(!result.contains("newpkg"));
+}
+
+#[test]
+fn test_groups_direct_and_transitive_changes() {
+    let report = Report {
+        bumps: vec![
+            VersionBump {
+                package_name: "requests".to_string(),
+                old_version: "1.0.0".to_string(),
+                new_version: "2.0.0".to_string(),
+                is_breaking: true,
+                ..Default::default()
+            },
+            VersionBump {
+                package_name: "urllib3".to_string(),
+                old_version: "1.26.0".to_string(),
+                new_version: "2.0.0".to_string(),
+                is_breaking: true,
+                ..Default::default()
+            },
+        ],
+        direct_dependencies: Some(["requests".to_string()].into_iter().collect()),
+        ..Default::default()
+    };
+
+    let result = format_text(&report, false);
+
+    let direct = result.find("Direct dependencies:").unwrap();
+    let transitive = result.find("Transitive dependencies:").unwrap();
+    let requests = result.find("requests").unwrap();
+    let urllib3 = result.find("urllib3").unwrap();
+    assert!(direct < requests && requests < transitive && transitive < urllib3);
+}
+
+#[test]
+fn test_shows_dependency_paths() {
+    let bumps = vec![VersionBump {
+        package_name: "urllib3".to_string(),
+        old_version: "1.26.0".to_string(),
+        new_version: "2.0.0".to_string(),
+        is_breaking: true,
+        paths: vec![vec![
+            "boto3".to_string(),
+            "botocore".to_string(),
+            "urllib3".to_string(),
+        ]],
+        ..Default::default()
+    }];
+
+    let result = format_text(&report_with(bumps), false);
+
+    assert!(result.contains("via boto3 → botocore → urllib3"));
+}
+
+#[test]
+fn test_shows_dependent_counts_for_breaking_changes() {
+    let bumps = vec![VersionBump {
+        package_name: "protobuf".to_string(),
+        old_version: "3.20.0".to_string(),
+        new_version: "4.21.0".to_string(),
+        is_breaking: true,
+        direct_dependents: vec!["grpcio".to_string()],
+        dependents: vec!["grpcio".to_string(), "googleapis".to_string()],
+        ..Default::default()
+    }];
+
+    let result = format_text(&report_with(bumps), false);
+
+    assert!(result.contains("protobuf: 3.20.0 → 4.21.0 (MAJOR) [2 dependent(s), 1 direct]"));
+}
+
+fn finding(severity: Severity) -> Finding {
+    Finding {
+        package_name: "requests".to_string(),
+        kind: FindingKind::DependencyAdded,
+        severity,
+        message: "now requires idna >=2.5,<4".to_string(),
+    }
+}
+
+#[test]
+fn test_low_severity_findings_require_all_flag() {
+    let report = Report {
+        findings: vec![finding(Severity::Low)],
+        ..Default::default()
+    };
+
+    let result = format_text(&report, false);
+    assert_eq!(result, "No breaking changes detected.");
+
+    let result = format_text(&report, true);
+    assert!(result.contains("Findings:"));
+    assert!(result.contains("requests: now requires idna >=2.5,<4 (dependency-added, low)"));
+}
+
+#[test]
+fn test_high_severity_findings_always_shown() {
+    let report = Report {
+        findings: vec![finding(Severity::High)],
+        ..Default::default()
+    };
+
+    let result = format_text(&report, false);
+
+    assert!(result.contains("Findings:"));
+    assert!(result.contains("(dependency-added, high)"));
+}
+
 // Tests for format_json
 
 #[test]
@@ -83,9 +240,10 @@ fn test_valid_json_output() {
         old_version: "1.0.0".to_string(),
         new_version: "2.0.0".to_string(),
         is_breaking: true,
+        ..Default::default()
     }];
 
-    let result = format_json(&bumps, false);
+    let result = format_json(&report_with(bumps), false);
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     assert!(parsed.is_object());
@@ -98,9 +256,10 @@ fn test_contains_breaking_changes_key() {
         old_version: "1.0.0".to_string(),
         new_version: "2.0.0".to_string(),
         is_breaking: true,
+        ..Default::default()
     }];
 
-    let result = format_json(&bumps, false);
+    let result = format_json(&report_with(bumps), false);
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     assert!(parsed.get("breaking_changes").is_some());
@@ -113,9 +272,10 @@ fn test_includes_package_details() {
         old_version: "1.0.0".to_string(),
         new_version: "2.0.0".to_string(),
         is_breaking: true,
+        ..Default::default()
     }];
 
-    let result = format_json(&bumps, false);
+    let result = format_json(&report_with(bumps), false);
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     let change = &parsed["breaking_changes"][0];
@@ -132,16 +292,18 @@ fn test_all_flag_includes_non_breaking() {
             old_version: "1.0.0".to_string(),
             new_version: "2.0.0".to_string(),
             is_breaking: true,
+            ..Default::default()
         },
         VersionBump {
             package_name: "pkg2".to_string(),
             old_version: "1.0.0".to_string(),
             new_version: "1.1.0".to_string(),
             is_breaking: false,
+            ..Default::default()
         },
     ];
 
-    let result = format_json(&bumps, true);
+    let result = format_json(&report_with(bumps), true);
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     assert_eq!(parsed["breaking_changes"].as_array().unwrap().len(), 1);
@@ -157,18 +319,771 @@ fn test_all_false_filters_non_breaking() {
             old_version: "1.0.0".to_string(),
             new_version: "2.0.0".to_string(),
             is_breaking: true,
+            ..Default::default()
         },
         VersionBump {
             package_name: "pkg2".to_string(),
             old_version: "1.0.0".to_string(),
             new_version: "1.1.0".to_string(),
             is_breaking: false,
+            ..Default::default()
         },
     ];
 
-    let result = format_json(&bumps, false);
+    let result = format_json(&report_with(bumps), false);
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     assert_eq!(parsed["breaking_changes"].as_array().unwrap().len(), 1);
     assert!(parsed.get("non_breaking_changes").is_none());
 }
+
+#[test]
+fn test_all_flag_includes_added_and_removed_packages_json() {
+    let report = Report {
+        added: vec![LockedPackage {
+            name: "newpkg".to_string(),
+            version: "1.0.0".to_string(),
+            ..Default::default()
+        }],
+        removed: vec![LockedPackage {
+            name: "oldpkg".to_string(),
+            version: "0.1.0".to_string(),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    let result = format_json(&report, true);
+
+    let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
+    assert_eq!(parsed["added_packages"][0]["package"], "newpkg");
+    assert_eq!(parsed["added_packages"][0]["version"], "1.0.0");
+    assert_eq!(parsed["removed_packages"][0]["package"], "oldpkg");
+}
+
+#[test]
+fn test_marks_direct_dependencies_json() {
+    let report = Report {
+        bumps: vec![VersionBump {
+            package_name: "urllib3".to_string(),
+            old_version: "1.26.0".to_string(),
+            new_version: "2.0.0".to_string(),
+            is_breaking: true,
+            ..Default::default()
+        }],
+        direct_dependencies: Some(["requests".to_string()].into_iter().collect()),
+        ..Default::default()
+    };
+
+    let result = format_json(&report, false);
+
+    let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
+    assert_eq!(parsed["breaking_changes"][0]["direct"], false);
+
+    let result = format_json(&report_with(report.bumps.clone()), false);
+
+    let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
+    assert!(parsed["breaking_changes"][0].get("direct").is_none());
+}
+
+#[test]
+fn test_includes_dependency_paths_json() {
+    let bumps = vec![VersionBump {
+        package_name: "urllib3".to_string(),
+        old_version: "1.26.0".to_string(),
+        new_version: "2.0.0".to_string(),
+        is_breaking: true,
+        paths: vec![vec!["requests".to_string(), "urllib3".to_string()]],
+        ..Default::default()
+    }];
+
+    let result = format_json(&report_with(bumps), false);
+
+    let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
+    assert_eq!(
+        parsed["breaking_changes"][0]["paths"],
+        serde_json::json!([["requests", "urllib3"]])
+    );
+}
+
+#[test]
+fn test_includes_dependents_json() {
+    let bumps = vec![VersionBump {
+        package_name: "protobuf".to_string(),
+        old_version: "3.20.0".to_string(),
+        new_version: "4.21.0".to_string(),
+        is_breaking: true,
+        direct_dependents: vec!["grpcio".to_string()],
+        dependents: vec!["googleapis".to_string(), "grpcio".to_string()],
+        ..Default::default()
+    }];
+
+    let result = format_json(&report_with(bumps), false);
+
+    let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
+    let change = &parsed["breaking_changes"][0];
+    assert_eq!(change["dependent_count"], 2);
+    assert_eq!(change["direct_dependents"], serde_json::json!(["grpcio"]));
+    assert_eq!(
+        change["dependents"],
+        serde_json::json!(["googleapis", "grpcio"])
+    );
+}
+
+#[test]
+fn test_includes_findings_json() {
+    let report = Report {
+        findings: vec![finding(Severity::Low)],
+        ..Default::default()
+    };
+
+    let parsed: serde_json::Value = serde_json::from_str(&format_json(&report, false)).unwrap();
+    assert!(parsed.get("findings").is_none());
+
+    let parsed: serde_json::Value = serde_json::from_str(&format_json(&report, true)).unwrap();
+    let finding = &parsed["findings"][0];
+    assert_eq!(finding["package"], "requests");
+    assert_eq!(finding["kind"], "dependency-added");
+    assert_eq!(finding["severity"], "low");
+    assert_eq!(finding["message"], "now requires idna >=2.5,<4");
+}
+
+fn matrix_report() -> Report {
+    let breaking = VersionBump {
+        package_name: "colorama".to_string(),
+        old_version: "0.4.6".to_string(),
+        new_version: "1.0.0".to_string(),
+        is_breaking: true,
+        ..Default::default()
+    };
+    let non_breaking = VersionBump {
+        package_name: "click".to_string(),
+        old_version: "8.1.0".to_string(),
+        new_version: "8.1.7".to_string(),
+        ..Default::default()
+    };
+
+    Report {
+        bumps: vec![non_breaking.clone(), breaking.clone()],
+        environments: vec![
+            EnvironmentBumps {
+                environment: "3.12:linux".parse().unwrap(),
+                bumps: vec![non_breaking.clone()],
+            },
+            EnvironmentBumps {
+                environment: "3.12:windows".parse().unwrap(),
+                bumps: vec![non_breaking, breaking],
+            },
+        ],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_formats_environments() {
+    let result = format_text(&matrix_report(), false);
+
+    assert!(result.contains(
+        "Environments:\n  3.12:linux: no breaking changes\n  \
+         3.12:windows: 1 breaking change(s)\n    colorama: 0.4.6 → 1.0.0 (MAJOR)"
+    ));
+    assert!(!result.contains("click"));
+
+    let result = format_text(&matrix_report(), true);
+    assert!(result.contains("  3.12:linux: no breaking changes\n    click: 8.1.0 → 8.1.7"));
+}
+
+#[test]
+fn test_includes_environments_json() {
+    let parsed: serde_json::Value =
+        serde_json::from_str(&format_json(&matrix_report(), false)).unwrap();
+
+    let environments = parsed["environments"].as_array().unwrap();
+    assert_eq!(environments.len(), 2);
+    assert_eq!(environments[0]["environment"], "3.12:linux");
+    assert_eq!(environments[0]["breaking_changes"], serde_json::json!([]));
+    assert!(environments[0].get("non_breaking_changes").is_none());
+    assert_eq!(
+        environments[1]["breaking_changes"][0]["package"],
+        "colorama"
+    );
+
+    let parsed: serde_json::Value =
+        serde_json::from_str(&format_json(&matrix_report(), true)).unwrap();
+    assert_eq!(
+        parsed["environments"][0]["non_breaking_changes"][0]["package"],
+        "click"
+    );
+}
+
+fn footprint_report() -> Report {
+    Report {
+        footprint: Some(Footprint {
+            old_packages: 40,
+            new_packages: 42,
+            old_artifacts: 300,
+            new_artifacts: 310,
+            old_size: Some(100 * 1024 * 1024),
+            new_size: Some(200 * 1024 * 1024),
+            size_changes: vec![SizeChange {
+                package_name: "torch".to_string(),
+                old_size: Some(100 * 1024 * 1024),
+                new_size: Some(200 * 1024 * 1024),
+            }],
+        }),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_formats_footprint() {
+    let result = format_text(&footprint_report(), false);
+
+    assert_eq!(
+        result,
+        "No breaking changes detected.\n\n\
+         Footprint:\n  \
+         Packages: 40 → 42 (+2)\n  \
+         Artifacts: 300 → 310 (+10)\n  \
+         Size: 100.0 MiB → 200.0 MiB (+100.0 MiB)\n  \
+         Size changes:\n    \
+         torch: 100.0 MiB → 200.0 MiB (+100.0 MiB)"
+    );
+}
+
+#[test]
+fn test_includes_footprint_json() {
+    let parsed: serde_json::Value =
+        serde_json::from_str(&format_json(&footprint_report(), false)).unwrap();
+
+    let footprint = &parsed["footprint"];
+    assert_eq!(footprint["old_package_count"], 40);
+    assert_eq!(footprint["new_artifact_count"], 310);
+    assert_eq!(footprint["new_size"], 200 * 1024 * 1024);
+    assert_eq!(footprint["size_changes"][0]["package"], "torch");
+    assert_eq!(footprint["size_changes"][0]["delta"], 100 * 1024 * 1024);
+
+    let parsed: serde_json::Value =
+        serde_json::from_str(&format_json(&report_with(vec![]), false)).unwrap();
+    assert!(parsed.get("footprint").is_none());
+}
+
+fn metadata_report() -> Report {
+    Report {
+        metadata_changes: vec![
+            MetadataChange {
+                field: "generator",
+                old_value: Some("Poetry 1.8.3".to_string()),
+                new_value: Some("Poetry 2.0.1".to_string()),
+            },
+            MetadataChange {
+                field: "python-versions",
+                old_value: None,
+                new_value: Some("^3.10".to_string()),
+            },
+        ],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_formats_metadata_changes() {
+    let result = format_text(&metadata_report(), false);
+
+    assert_eq!(
+        result,
+        "No breaking changes detected.\n\n\
+         Lock metadata:\n  \
+         generator: Poetry 1.8.3 → Poetry 2.0.1\n  \
+         python-versions: - → ^3.10"
+    );
+}
+
+#[test]
+fn test_includes_metadata_changes_json() {
+    let parsed: serde_json::Value =
+        serde_json::from_str(&format_json(&metadata_report(), false)).unwrap();
+
+    let changes = &parsed["metadata_changes"];
+    assert_eq!(changes[0]["field"], "generator");
+    assert_eq!(changes[0]["old"], "Poetry 1.8.3");
+    assert_eq!(changes[0]["new"], "Poetry 2.0.1");
+    assert!(changes[1]["old"].is_null());
+}
+
+fn constraint_report() -> Report {
+    Report {
+        bumps: vec![VersionBump {
+            package_name: "click".to_string(),
+            old_version: "8.1.7".to_string(),
+            new_version: "9.0.0".to_string(),
+            is_breaking: true,
+            ..Default::default()
+        }],
+        constraint_changes: vec![
+            ConstraintChange {
+                package_name: "click".to_string(),
+                group: "main".to_string(),
+                old_constraint: Some("^8.0".to_string()),
+                new_constraint: Some("^9.0".to_string()),
+                kind: ConstraintChangeKind::Changed,
+            },
+            ConstraintChange {
+                package_name: "rich".to_string(),
+                group: "dev".to_string(),
+                old_constraint: None,
+                new_constraint: Some("^13.0".to_string()),
+                kind: ConstraintChangeKind::Added,
+            },
+        ],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_formats_constraint_changes() {
+    let result = format_text(&constraint_report(), false);
+
+    assert!(result.ends_with(
+        "Constraint changes:\n  \
+         click (main): ^8.0 → ^9.0 (changed); locked 8.1.7 → 9.0.0\n  \
+         rich (dev): - → ^13.0 (added)"
+    ));
+}
+
+#[test]
+fn test_includes_constraint_changes_json() {
+    let parsed: serde_json::Value =
+        serde_json::from_str(&format_json(&constraint_report(), false)).unwrap();
+
+    let changes = &parsed["constraint_changes"];
+    assert_eq!(changes[0]["package"], "click");
+    assert_eq!(changes[0]["kind"], "changed");
+    assert_eq!(changes[0]["new_version"], "9.0.0");
+    assert!(changes[1]["old_constraint"].is_null());
+    assert!(changes[1].get("new_version").is_none());
+}
+
+// Tests for format_markdown
+
+fn markdown_report() -> Report {
+    Report {
+        bumps: vec![
+            VersionBump {
+                package_name: "numpy".to_string(),
+                old_version: "1.26.0".to_string(),
+                new_version: "2.0.0".to_string(),
+                is_breaking: true,
+                dependents: vec!["pandas".to_string(), "scipy".to_string()],
+                direct_dependents: vec!["pandas".to_string()],
+                ..Default::default()
+            },
+            VersionBump {
+                package_name: "requests".to_string(),
+                old_version: "2.31.0".to_string(),
+                new_version: "2.32.0".to_string(),
+                is_breaking: false,
+                ..Default::default()
+            },
+        ],
+        added: vec![LockedPackage {
+            name: "rich".to_string(),
+            version: "13.7.0".to_string(),
+            ..Default::default()
+        }],
+        findings: vec![Finding {
+            package_name: "idna".to_string(),
+            kind: FindingKind::HashChanged,
+            severity: Severity::High,
+            message: "artifacts changed: a | b".to_string(),
+        }],
+        direct_dependencies: Some(["numpy".to_string()].into_iter().collect()),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_formats_markdown() {
+    assert_eq!(
+        format_markdown(&markdown_report(), false),
+        "## pdrift report\n\
+         \n\
+         **1 breaking change(s)** · 1 non-breaking · 1 added · 0 removed · 1 finding(s)\n\
+         \n\
+         ### Breaking changes\n\
+         \n\
+         | Package | Dependency | Version | Change | Dependents |\n\
+         |---|---|---|---|---|\n\
+         | `numpy` | direct | 1.26.0 → 2.0.0 | ![MAJOR](https://img.shields.io/badge/MAJOR-red) | 2 (1 direct) |\n\
+         \n\
+         ### Findings\n\
+         \n\
+         | Package | Kind | Severity | Details |\n\
+         |---|---|---|---|\n\
+         | `idna` | ![hash-changed](https://img.shields.io/badge/hash--changed-blue) \
+         | ![high](https://img.shields.io/badge/high-red) | artifacts changed: a \\| b |\n\
+         \n\
+         <details>\n\
+         <summary>Non-breaking changes (1)</summary>\n\
+         \n\
+         | Package | Version | Change |\n\
+         |---|---|---|\n\
+         | `requests` | 2.31.0 → 2.32.0 | ![minor](https://img.shields.io/badge/minor-yellow) |\n\
+         \n\
+         </details>\n\
+         \n\
+         <details>\n\
+         <summary>Added packages (1)</summary>\n\
+         \n\
+         | Package | Version |\n\
+         |---|---|\n\
+         | `rich` | 13.7.0 |\n\
+         \n\
+         </details>"
+    );
+}
+
+#[test]
+fn test_formats_markdown_without_changes() {
+    assert_eq!(
+        format_markdown(&Report::default(), false),
+        "## pdrift report\n\n\
+         No breaking changes detected · 0 non-breaking · 0 added · 0 removed · 0 finding(s)"
+    );
+}
+
+#[test]
+fn test_markdown_includes_requested_sections() {
+    let report = Report {
+        environments: matrix_report().environments,
+        constraint_changes: constraint_report().constraint_changes,
+        metadata_changes: metadata_report().metadata_changes,
+        footprint: footprint_report().footprint,
+        ..constraint_report()
+    };
+
+    let result = format_markdown(&report, false);
+
+    assert!(result.contains(
+        "<summary>Environments (2)</summary>\n\
+         \n\
+         | Environment | Breaking changes | Non-breaking changes |\n\
+         |---|---|---|\n\
+         | `3.12:linux` | - | `click` 8.1.0 → 8.1.7 |\n\
+         | `3.12:windows` | `colorama` 0.4.6 → 1.0.0 | `click` 8.1.0 → 8.1.7 |"
+    ));
+    assert!(result.contains(
+        "| `click` | main | ^8.0 | ^9.0 | changed | 8.1.7 → 9.0.0 |\n\
+         | `rich` | dev | - | ^13.0 | added | - |"
+    ));
+    assert!(result.contains("| `generator` | Poetry 1.8.3 | Poetry 2.0.1 |"));
+    assert!(result.contains("| Size | 100.0 MiB | 200.0 MiB | +100.0 MiB |"));
+    assert!(result.contains("| `torch` | 100.0 MiB | 200.0 MiB | +100.0 MiB |"));
+}
+
+// Tests for format_sarif
+
+#[test]
+fn test_formats_sarif() {
+    let report = Report {
+        new_lock: Some(LockLocation {
+            path: "app/poetry.lock".to_string(),
+            package_lines: [("numpy".to_string(), 42)].into_iter().collect(),
+        }),
+        ..markdown_report()
+    };
+
+    let parsed: serde_json::Value = serde_json::from_str(&format_sarif(&report, false)).unwrap();
+    assert_eq!(parsed["version"], "2.1.0");
+
+    let run = &parsed["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "pdrift");
+    let rules: Vec<&str> = run["tool"]["driver"]["rules"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(rules, vec!["breaking-bump", "hash-changed"]);
+
+    let results = run["results"].as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0]["ruleId"], "breaking-bump");
+    assert_eq!(results[0]["ruleIndex"], 0);
+    assert_eq!(results[0]["level"], "error");
+    assert_eq!(
+        results[0]["message"]["text"],
+        "numpy: 1.26.0 → 2.0.0 (MAJOR)"
+    );
+    let location = &results[0]["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "app/poetry.lock");
+    assert_eq!(location["artifactLocation"]["uriBaseId"], "%SRCROOT%");
+    assert_eq!(location["region"]["startLine"], 42);
+
+    assert_eq!(results[1]["ruleId"], "hash-changed");
+    assert_eq!(results[1]["ruleIndex"], 1);
+    assert!(results[1]["locations"][0]["physicalLocation"]
+        .get("region")
+        .is_none());
+}
+
+#[test]
+fn test_sarif_uses_file_uris_for_absolute_paths() {
+    let artifact_uri = |path: &str| {
+        let report = Report {
+            new_lock: Some(LockLocation {
+                path: path.to_string(),
+                ..Default::default()
+            }),
+            ..markdown_report()
+        };
+        let parsed: serde_json::Value =
+            serde_json::from_str(&format_sarif(&report, false)).unwrap();
+        parsed["runs"][0]["results"][0]["locations"][0]["physicalLocation"]["artifactLocation"]
+            .clone()
+    };
+
+    let location = artifact_uri("/tmp/my app/poetry.lock");
+    assert_eq!(location["uri"], "file:///tmp/my%20app/poetry.lock");
+    assert!(location.get("uriBaseId").is_none());
+
+    let location = artifact_uri(r"C:\work\poetry.lock");
+    assert_eq!(location["uri"], "file:///C:/work/poetry.lock");
+
+    let location = artifact_uri("./poetry.lock");
+    assert_eq!(location["uri"], "poetry.lock");
+    assert_eq!(location["uriBaseId"], "%SRCROOT%");
+}
+
+#[test]
+fn test_sarif_includes_non_breaking_bumps_with_all() {
+    let parsed: serde_json::Value =
+        serde_json::from_str(&format_sarif(&markdown_report(), true)).unwrap();
+
+    let results = parsed["runs"][0]["results"].as_array().unwrap();
+    let levels: Vec<(&str, &str)> = results
+        .iter()
+        .map(|r| (r["ruleId"].as_str().unwrap(), r["level"].as_str().unwrap()))
+        .collect();
+    assert_eq!(
+        levels,
+        vec![
+            ("breaking-bump", "error"),
+            ("non-breaking-bump", "note"),
+            ("hash-changed", "error"),
+        ]
+    );
+    assert!(results[0].get("locations").is_none());
+}
+
+// Tests for format_junit
+
+#[test]
+fn test_formats_junit() {
+    let report = Report {
+        checked_packages: vec![
+            "idna".to_string(),
+            "numpy".to_string(),
+            "requests".to_string(),
+            "rich".to_string(),
+            "six".to_string(),
+        ],
+        new_lock: Some(LockLocation {
+            path: "poetry.lock".to_string(),
+            ..Default::default()
+        }),
+        ..markdown_report()
+    };
+
+    assert_eq!(
+        format_junit(&report, false),
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="pdrift" tests="5" failures="2" errors="0">
+  <testsuite name="poetry.lock" tests="5" failures="2" errors="0" skipped="0" time="0">
+    <testcase classname="pdrift.transitive" name="idna" time="0">
+      <failure type="hash-changed" message="artifacts changed: a | b">artifacts changed: a | b (hash-changed, high)</failure>
+    </testcase>
+    <testcase classname="pdrift.direct" name="numpy" time="0">
+      <failure type="MAJOR" message="breaking bump 1.26.0 → 2.0.0 (MAJOR)">old version: 1.26.0
+new version: 2.0.0
+change: MAJOR</failure>
+    </testcase>
+    <testcase classname="pdrift.transitive" name="requests" time="0">
+      <system-out>2.31.0 → 2.32.0 (minor)</system-out>
+    </testcase>
+    <testcase classname="pdrift.transitive" name="rich" time="0">
+      <system-out>added 13.7.0</system-out>
+    </testcase>
+    <testcase classname="pdrift.transitive" name="six" time="0"/>
+  </testsuite>
+</testsuites>"#
+    );
+}
+
+#[test]
+fn test_junit_escapes_xml() {
+    let report = Report {
+        checked_packages: vec!["pkg".to_string()],
+        findings: vec![Finding {
+            package_name: "pkg".to_string(),
+            kind: FindingKind::SourceChanged,
+            severity: Severity::High,
+            message: "moved to <git> & \"elsewhere\"".to_string(),
+        }],
+        ..Default::default()
+    };
+
+    let result = format_junit(&report, false);
+
+    assert!(result.contains(r#"<testsuite name="pdrift" tests="1" failures="1""#));
+    assert!(result.contains(r#"message="moved to &lt;git&gt; &amp; &quot;elsewhere&quot;""#));
+}
+
+// Tests for format_check_text and format_check_json
+
+fn stale_check() -> CheckResult {
+    CheckResult {
+        lock_hash: Some("aaaa".to_string()),
+        pyproject_hash: "bbbb".to_string(),
+        mismatches: vec![DeclarationMismatch {
+            package_name: "mypy".to_string(),
+            group: "dev".to_string(),
+            constraint: "*".to_string(),
+            problem: "not in the lock".to_string(),
+        }],
+    }
+}
+
+#[test]
+fn test_formats_stale_check() {
+    assert_eq!(
+        format_check_text(&stale_check()),
+        "The lock file is stale: content-hash aaaa does not match pyproject.toml (bbbb).\n\
+         Declarations not satisfied by the lock file:\n  \
+         mypy * (dev): not in the lock"
+    );
+}
+
+#[test]
+fn test_formats_up_to_date_check() {
+    let result = CheckResult {
+        lock_hash: Some("aaaa".to_string()),
+        pyproject_hash: "aaaa".to_string(),
+        mismatches: vec![],
+    };
+
+    assert_eq!(
+        format_check_text(&result),
+        "The lock file is up to date with pyproject.toml."
+    );
+}
+
+#[test]
+fn test_formats_check_json() {
+    let parsed: serde_json::Value =
+        serde_json::from_str(&format_check_json(&stale_check())).unwrap();
+
+    assert_eq!(parsed["stale"], true);
+    assert_eq!(parsed["lock_content_hash"], "aaaa");
+    assert_eq!(parsed["pyproject_content_hash"], "bbbb");
+    assert_eq!(parsed["mismatches"][0]["package"], "mypy");
+    assert_eq!(parsed["mismatches"][0]["problem"], "not in the lock");
+}
+
+// Tests for format_lint_text and format_lint_json
+
+fn lint_issue() -> LintIssue {
+    LintIssue {
+        package_name: "requests".to_string(),
+        group: "main".to_string(),
+        constraint: ">=2.0".to_string(),
+        locked_version: "2.31.0".to_string(),
+        allowed_version: "3.0.0".to_string(),
+        suggestion: "^2.31.0".to_string(),
+        pep440_suggestion: ">=2.31.0,<3.0.0".to_string(),
+    }
+}
+
+#[test]
+fn test_formats_lint_issues() {
+    assert_eq!(
+        format_lint_text(&[lint_issue()]),
+        "Constraints allowing a breaking bump:\n  \
+         requests >=2.0 (main): locked 2.31.0, allows 3.0.0; suggest ^2.31.0 (>=2.31.0,<3.0.0)\n\
+         1 constraint(s) found."
+    );
+    assert_eq!(
+        format_lint_text(&[]),
+        "No constraints allow a breaking bump."
+    );
+}
+
+#[test]
+fn test_formats_lint_json() {
+    let parsed: serde_json::Value =
+        serde_json::from_str(&format_lint_json(&[lint_issue()])).unwrap();
+
+    let issue = &parsed["issues"][0];
+    assert_eq!(issue["package"], "requests");
+    assert_eq!(issue["allowed_version"], "3.0.0");
+    assert_eq!(issue["suggestion"], "^2.31.0");
+}
+
+// Tests for format_validate_text and format_validate_json
+
+fn invalid_lock() -> ValidationResult {
+    ValidationResult {
+        missing: vec![UnmetRequirement {
+            package_name: "requests".to_string(),
+            package_version: "2.31.0".to_string(),
+            dependency_name: "certifi".to_string(),
+            constraint: ">=2017.4.17".to_string(),
+            locked_version: None,
+        }],
+        unsatisfied: vec![UnmetRequirement {
+            package_name: "requests".to_string(),
+            package_version: "2.31.0".to_string(),
+            dependency_name: "idna".to_string(),
+            constraint: ">=2.5,<4".to_string(),
+            locked_version: Some("4.0".to_string()),
+        }],
+        cycles: vec![vec!["a".to_string(), "b".to_string(), "a".to_string()]],
+    }
+}
+
+#[test]
+fn test_formats_validation_problems() {
+    assert_eq!(
+        format_validate_text(&invalid_lock()),
+        "Requirements missing from the lock file:\n  \
+         requests 2.31.0 requires certifi >=2017.4.17\n\
+         Requirements not satisfied by the lock file:\n  \
+         requests 2.31.0 requires idna >=2.5,<4, locked 4.0\n\
+         Dependency cycles:\n  \
+         a → b → a\n\
+         2 problem(s) found."
+    );
+    assert_eq!(
+        format_validate_text(&ValidationResult {
+            cycles: invalid_lock().cycles,
+            ..Default::default()
+        }),
+        "Dependency cycles:\n  a → b → a\nThe lock file is consistent."
+    );
+    assert_eq!(
+        format_validate_text(&ValidationResult::default()),
+        "The lock file is consistent."
+    );
+}
+
+#[test]
+fn test_formats_validation_json() {
+    let parsed: serde_json::Value =
+        serde_json::from_str(&format_validate_json(&invalid_lock())).unwrap();
+
+    assert_eq!(parsed["valid"], false);
+    assert_eq!(parsed["missing"][0]["dependency"], "certifi");
+    assert!(parsed["missing"][0].get("locked_version").is_none());
+    assert_eq!(parsed["unsatisfied"][0]["locked_version"], "4.0");
+    assert_eq!(parsed["cycles"][0][1], "b");
+}
@@ -0,0 +1,240 @@
+use pdrift_rs::compare::VersionBump;
+use pdrift_rs::findings::{Finding, FindingKind, Severity};
+use pdrift_rs::lockfile::LockedPackage;
+use pdrift_rs::policy::{
+    evaluate, FailOn, Outcome, Policy, EXIT_OK, EXIT_POLICY_VIOLATION, EXIT_WARNINGS,
+};
+use pdrift_rs::report::Report;
+
+fn bump(old_version: &str, new_version: &str, is_breaking: bool) -> VersionBump {
+    VersionBump {
+        package_name: "pkg".to_string(),
+        old_version: old_version.to_string(),
+        new_version: new_version.to_string(),
+        is_breaking,
+        ..Default::default()
+    }
+}
+
+fn policy(fail_on: &[FailOn]) -> Policy {
+    Policy {
+        fail_on: fail_on.to_vec(),
+        direct_only: false,
+    }
+}
+
+fn added_report() -> Report {
+    Report {
+        added: vec![LockedPackage {
+            name: "newpkg".to_string(),
+            version: "1.0.0".to_string(),
+            ..Default::default()
+        }],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_clean_report() {
+    let report = Report::default();
+
+    assert_eq!(
+        evaluate(&report, &policy(&[FailOn::Breaking])),
+        Outcome::Clean
+    );
+    assert_eq!(
+        evaluate(&report, &policy(&[FailOn::AnyChange])),
+        Outcome::Clean
+    );
+}
+
+#[test]
+fn test_breaking_violation() {
+    let report = Report {
+        bumps: vec![bump("1.0.0", "2.0.0", true)],
+        ..Default::default()
+    };
+
+    assert_eq!(
+        evaluate(&report, &policy(&[FailOn::Breaking])),
+        Outcome::Violation
+    );
+}
+
+#[test]
+fn test_breaking_is_warning_when_allowed() {
+    let report = Report {
+        bumps: vec![bump("1.0.0", "2.0.0", true)],
+        ..Default::default()
+    };
+
+    assert_eq!(
+        evaluate(&report, &policy(&[FailOn::Never])),
+        Outcome::Warnings
+    );
+    assert_eq!(
+        evaluate(&report, &policy(&[FailOn::Added])),
+        Outcome::Warnings
+    );
+}
+
+#[test]
+fn test_minor_threshold() {
+    let report = Report {
+        bumps: vec![bump("1.1.0", "1.2.0", false)],
+        ..Default::default()
+    };
+
+    assert_eq!(
+        evaluate(&report, &policy(&[FailOn::Breaking])),
+        Outcome::Clean
+    );
+    assert_eq!(
+        evaluate(&report, &policy(&[FailOn::Minor])),
+        Outcome::Violation
+    );
+}
+
+#[test]
+fn test_minor_threshold_with_unparseable_version() {
+    let report = Report {
+        bumps: vec![bump("1.1.0", "not-a-version", false)],
+        ..Default::default()
+    };
+
+    assert_eq!(evaluate(&report, &policy(&[FailOn::Minor])), Outcome::Clean);
+}
+
+#[test]
+fn test_patch_only_fails_on_any_change() {
+    let report = Report {
+        bumps: vec![bump("1.1.0", "1.1.1", false)],
+        ..Default::default()
+    };
+
+    assert_eq!(evaluate(&report, &policy(&[FailOn::Minor])), Outcome::Clean);
+    assert_eq!(
+        evaluate(&report, &policy(&[FailOn::AnyChange])),
+        Outcome::Violation
+    );
+}
+
+#[test]
+fn test_added_and_removed_rules() {
+    let report = added_report();
+
+    assert_eq!(
+        evaluate(&report, &policy(&[FailOn::Breaking])),
+        Outcome::Clean
+    );
+    assert_eq!(
+        evaluate(&report, &policy(&[FailOn::Removed])),
+        Outcome::Clean
+    );
+    assert_eq!(
+        evaluate(&report, &policy(&[FailOn::Added])),
+        Outcome::Violation
+    );
+    assert_eq!(
+        evaluate(&report, &policy(&[FailOn::AnyChange])),
+        Outcome::Violation
+    );
+    assert_eq!(
+        evaluate(&report, &policy(&[FailOn::Breaking, FailOn::Added])),
+        Outcome::Violation
+    );
+}
+
+#[test]
+fn test_direct_only_downgrades_transitive_changes() {
+    let mut transitive = bump("1.0.0", "2.0.0", true);
+    transitive.package_name = "urllib3".to_string();
+    let report = Report {
+        bumps: vec![transitive],
+        direct_dependencies: Some(["requests".to_string()].into_iter().collect()),
+        ..Default::default()
+    };
+
+    let direct_only = Policy {
+        fail_on: vec![FailOn::Breaking],
+        direct_only: true,
+    };
+
+    assert_eq!(evaluate(&report, &direct_only), Outcome::Warnings);
+    assert_eq!(
+        evaluate(&report, &policy(&[FailOn::Breaking])),
+        Outcome::Violation
+    );
+}
+
+#[test]
+fn test_direct_only_fails_on_direct_changes() {
+    let mut direct = bump("1.0.0", "2.0.0", true);
+    direct.package_name = "Requests".to_string();
+    let report = Report {
+        bumps: vec![direct],
+        direct_dependencies: Some(["requests".to_string()].into_iter().collect()),
+        ..Default::default()
+    };
+
+    let direct_only = Policy {
+        fail_on: vec![FailOn::Breaking],
+        direct_only: true,
+    };
+
+    assert_eq!(evaluate(&report, &direct_only), Outcome::Violation);
+}
+
+fn findings_report(severity: Severity) -> Report {
+    Report {
+        findings: vec![Finding {
+            package_name: "pkg".to_string(),
+            kind: FindingKind::DependencyChanged,
+            severity,
+            message: "requirement changed".to_string(),
+        }],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_high_severity_findings_only_violate_high() {
+    let report = findings_report(Severity::High);
+
+    assert_eq!(
+        evaluate(&report, &policy(&[FailOn::High])),
+        Outcome::Violation
+    );
+    assert_eq!(
+        evaluate(&report, &policy(&[FailOn::Breaking])),
+        Outcome::Warnings
+    );
+    assert_eq!(
+        evaluate(&report, &policy(&[FailOn::AnyChange])),
+        Outcome::Warnings
+    );
+    assert_eq!(
+        evaluate(&report, &policy(&[FailOn::Never])),
+        Outcome::Warnings
+    );
+}
+
+#[test]
+fn test_low_severity_findings_never_fail() {
+    let report = findings_report(Severity::Low);
+
+    assert_eq!(
+        evaluate(&report, &policy(&[FailOn::High, FailOn::AnyChange])),
+        Outcome::Clean
+    );
+}
+
+#[test]
+fn test_exit_codes() {
+    assert_eq!(Outcome::Clean.exit_code(), EXIT_OK);
+    assert_eq!(Outcome::Violation.exit_code(), EXIT_POLICY_VIOLATION);
+    assert_eq!(Outcome::Warnings.exit_code(), EXIT_WARNINGS);
+    assert_eq!(EXIT_OK, 0);
+    assert_eq!(EXIT_POLICY_VIOLATION, 1);
+    assert_eq!(EXIT_WARNINGS, 3);
+}
@@ -0,0 +1,164 @@
+use pdrift_rs::pyproject::{parse_pyproject, parse_requirement};
+use std::path::Path;
+use tempfile::TempDir;
+
+fn write_pyproject(content: &str) -> (TempDir, std::path::PathBuf) {
+    let tmp_dir = TempDir::new().unwrap();
+    let path = tmp_dir.path().join("pyproject.toml");
+    std::fs::write(&path, content).unwrap();
+    (tmp_dir, path)
+}
+
+#[test]
+fn test_parse_requirement() {
+    assert_eq!(
+        parse_requirement("requests>=2.31"),
+        Some(("requests".to_string(), ">=2.31".to_string()))
+    );
+    assert_eq!(
+        parse_requirement("requests[socks] (>=2.0,<3); python_version > \"3.8\""),
+        Some(("requests".to_string(), ">=2.0,<3".to_string()))
+    );
+    assert_eq!(
+        parse_requirement("numpy"),
+        Some(("numpy".to_string(), "*".to_string()))
+    );
+    assert_eq!(
+        parse_requirement("mylib @ git+https://example.com/mylib.git"),
+        Some(("mylib".to_string(), "*".to_string()))
+    );
+}
+
+#[test]
+fn test_parse_poetry_dependencies() {
+    let (_tmp_dir, path) = write_pyproject(
+        r#"[tool.poetry.dependencies]
+python = "^3.10"
+requests = "^2.31"
+numpy = { version = ">=1.26", optional = true }
+mylib = { git = "https://example.com/mylib.git", rev = "v1.2.0" }
+locallib = { path = "../locallib", develop = true }
+
+[tool.poetry.group.dev.dependencies]
+pytest = "^8.0"
+"#,
+    );
+
+    let pyproject = parse_pyproject(&path).unwrap();
+    let deps = &pyproject.dependencies;
+
+    assert_eq!(deps.len(), 5);
+    assert!(!deps.iter().any(|d| d.name == "python"));
+
+    let requests = deps.iter().find(|d| d.name == "requests").unwrap();
+    assert_eq!(requests.constraint, "^2.31");
+    assert_eq!(requests.group, "main");
+
+    let numpy = deps.iter().find(|d| d.name == "numpy").unwrap();
+    assert_eq!(numpy.constraint, ">=1.26");
+
+    let mylib = deps.iter().find(|d| d.name == "mylib").unwrap();
+    assert_eq!(mylib.constraint, "*");
+    assert!(mylib.direct_reference);
+
+    let locallib = deps.iter().find(|d| d.name == "locallib").unwrap();
+    assert!(locallib.direct_reference);
+    assert!(!requests.direct_reference);
+
+    let pytest = deps.iter().find(|d| d.name == "pytest").unwrap();
+    assert_eq!(pytest.group, "dev");
+}
+
+#[test]
+fn test_parse_legacy_dev_dependencies() {
+    let (_tmp_dir, path) = write_pyproject(
+        r#"[tool.poetry.dev-dependencies]
+black = "^23.0"
+"#,
+    );
+
+    let pyproject = parse_pyproject(&path).unwrap();
+
+    assert_eq!(pyproject.dependencies.len(), 1);
+    assert_eq!(pyproject.dependencies[0].group, "dev");
+}
+
+#[test]
+fn test_parse_pep621_dependencies() {
+    let (_tmp_dir, path) = write_pyproject(
+        r#"[project]
+name = "example"
+dependencies = [
+    "requests>=2.31,<3",
+    "Django-CORS-Headers",
+    "mylib @ git+https://example.com/mylib.git@v1.2.0",
+]
+
+[project.optional-dependencies]
+numpy = ["numpy>=1.26"]
+
+[dependency-groups]
+test = ["pytest>=8"]
+"#,
+    );
+
+    let pyproject = parse_pyproject(&path).unwrap();
+    let deps = &pyproject.dependencies;
+
+    assert_eq!(deps.len(), 5);
+    let requests = deps.iter().find(|d| d.name == "requests").unwrap();
+    assert_eq!(requests.constraint, ">=2.31,<3");
+    assert_eq!(requests.group, "main");
+    assert!(!requests.direct_reference);
+    assert!(
+        deps.iter()
+            .find(|d| d.name == "mylib")
+            .unwrap()
+            .direct_reference
+    );
+    assert_eq!(
+        deps.iter().find(|d| d.name == "numpy").unwrap().group,
+        "numpy"
+    );
+    assert_eq!(
+        deps.iter().find(|d| d.name == "pytest").unwrap().group,
+        "test"
+    );
+}
+
+#[test]
+fn test_pep621_declaration_wins_over_poetry() {
+    let (_tmp_dir, path) = write_pyproject(
+        r#"[project]
+name = "example"
+dependencies = ["requests>=2.31"]
+
+[tool.poetry.dependencies]
+requests = { source = "internal" }
+"#,
+    );
+
+    let pyproject = parse_pyproject(&path).unwrap();
+
+    assert_eq!(pyproject.dependencies.len(), 1);
+    assert_eq!(pyproject.dependencies[0].constraint, ">=2.31");
+}
+
+#[test]
+fn test_direct_dependencies_are_normalized() {
+    let (_tmp_dir, path) = write_pyproject(
+        r#"[project]
+name = "example"
+dependencies = ["Django-CORS-Headers>=4"]
+"#,
+    );
+
+    let direct = parse_pyproject(&path).unwrap().direct_dependencies();
+
+    assert!(direct.contains("django_cors_headers"));
+}
+
+#[test]
+fn test_pyproject_not_found() {
+    assert!(parse_pyproject(Path::new("/nonexistent/pyproject.toml")).is_err());
+}
@@ -0,0 +1,161 @@
+use pdrift_rs::compare::VersionBump;
+use pdrift_rs::lockfile::{parse_lockfile, LockedPackage};
+use pdrift_rs::report::{Report, SortBy};
+use std::collections::HashMap;
+use std::path::Path;
+
+#[test]
+fn test_new_report_from_fixtures() {
+    let old = parse_lockfile(Path::new("tests/fixtures/poetry-old.lock")).unwrap();
+    let new = parse_lockfile(Path::new("tests/fixtures/poetry-new.lock")).unwrap();
+
+    let report = Report::new(&old, &new);
+
+    assert_eq!(report.bumps.len(), 5);
+    assert!(report.added.is_empty());
+    assert!(report.removed.is_empty());
+    assert!(report.direct_dependencies.is_none());
+}
+
+#[test]
+fn test_trace_dependencies_from_graph_roots() {
+    let old = parse_lockfile(Path::new("tests/fixtures/poetry-old.lock")).unwrap();
+    let new = parse_lockfile(Path::new("tests/fixtures/poetry-new.lock")).unwrap();
+
+    let mut report = Report::new(&old, &new);
+    report.trace_dependencies(&new);
+
+    let urllib3 = report
+        .bumps
+        .iter()
+        .find(|b| b.package_name == "urllib3")
+        .unwrap();
+    assert_eq!(urllib3.paths, vec![vec!["requests", "urllib3"]]);
+}
+
+#[test]
+fn test_trace_dependencies_from_direct_dependencies() {
+    let old = parse_lockfile(Path::new("tests/fixtures/poetry-old.lock")).unwrap();
+    let new = parse_lockfile(Path::new("tests/fixtures/poetry-new.lock")).unwrap();
+
+    let mut report = Report::new(&old, &new);
+    report.direct_dependencies = Some(["urllib3".to_string()].into_iter().collect());
+    report.trace_dependencies(&new);
+
+    let urllib3 = report
+        .bumps
+        .iter()
+        .find(|b| b.package_name == "urllib3")
+        .unwrap();
+    assert_eq!(urllib3.paths, vec![vec!["urllib3"]]);
+
+    let requests = report
+        .bumps
+        .iter()
+        .find(|b| b.package_name == "requests")
+        .unwrap();
+    assert!(requests.paths.is_empty());
+}
+
+#[test]
+fn test_trace_dependencies_records_dependents() {
+    let old = parse_lockfile(Path::new("tests/fixtures/poetry-old.lock")).unwrap();
+    let new = parse_lockfile(Path::new("tests/fixtures/poetry-new.lock")).unwrap();
+
+    let mut report = Report::new(&old, &new);
+    report.trace_dependencies(&new);
+
+    let urllib3 = report
+        .bumps
+        .iter()
+        .find(|b| b.package_name == "urllib3")
+        .unwrap();
+    assert_eq!(urllib3.direct_dependents, vec!["requests"]);
+    assert_eq!(urllib3.dependents, vec!["requests"]);
+}
+
+fn bump_with_dependents(name: &str, dependents: &[&str]) -> VersionBump {
+    VersionBump {
+        package_name: name.to_string(),
+        dependents: dependents.iter().map(|d| d.to_string()).collect(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_sort_bumps_by_dependents() {
+    let mut report = Report {
+        bumps: vec![
+            bump_with_dependents("alpha", &[]),
+            bump_with_dependents("protobuf", &["grpcio", "googleapis"]),
+            bump_with_dependents("beta", &["x"]),
+            bump_with_dependents("Aardvark", &["y"]),
+        ],
+        ..Default::default()
+    };
+
+    report.sort_bumps(SortBy::Dependents);
+    let names: Vec<&str> = report
+        .bumps
+        .iter()
+        .map(|b| b.package_name.as_str())
+        .collect();
+    assert_eq!(names, vec!["protobuf", "Aardvark", "beta", "alpha"]);
+
+    report.sort_bumps(SortBy::Name);
+    let names: Vec<&str> = report
+        .bumps
+        .iter()
+        .map(|b| b.package_name.as_str())
+        .collect();
+    assert_eq!(names, vec!["Aardvark", "alpha", "beta", "protobuf"]);
+}
+
+fn locked(name: &str, version: &str, markers: Option<&str>) -> (String, LockedPackage) {
+    (
+        name.to_string(),
+        LockedPackage {
+            name: name.to_string(),
+            version: version.to_string(),
+            markers: markers.map(String::from),
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+fn test_checked_packages_cover_both_locks() {
+    let old = HashMap::from([locked("idna", "3.6", None), locked("six", "1.16.0", None)]);
+    let new = HashMap::from([locked("idna", "3.7", None), locked("Rich", "13.7.0", None)]);
+
+    let report = Report::new(&old, &new);
+
+    assert_eq!(report.checked_packages, vec!["idna", "Rich", "six"]);
+}
+
+#[test]
+fn test_add_environment_keeps_applicable_bumps() {
+    let old = HashMap::from([
+        locked("click", "8.0.0", None),
+        locked("tomli", "1.2.0", Some(r#"python_version < "3.11""#)),
+    ]);
+    let new = HashMap::from([
+        locked("click", "9.0.0", None),
+        locked("tomli", "2.0.1", Some(r#"python_version < "3.11""#)),
+    ]);
+
+    let mut report = Report::new(&old, &new);
+    report.add_environment("3.10".parse().unwrap(), &old, &new);
+    report.add_environment("3.12".parse().unwrap(), &old, &new);
+
+    let names = |index: usize| -> Vec<String> {
+        report.environments[index]
+            .bumps
+            .iter()
+            .map(|b| b.package_name.clone())
+            .collect()
+    };
+    assert_eq!(report.environments[0].environment.to_string(), "3.10");
+    assert_eq!(names(0), vec!["click", "tomli"]);
+    assert_eq!(names(1), vec!["click"]);
+}
@@ -0,0 +1,167 @@
+use pdrift_rs::lockfile::parse_lockfile;
+use pdrift_rs::validate::{validate_lock, ValidationResult};
+use std::fs;
+use tempfile::TempDir;
+
+fn validate(lock: &str) -> ValidationResult {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("poetry.lock");
+    fs::write(&path, lock).unwrap();
+
+    validate_lock(&parse_lockfile(&path).unwrap())
+}
+
+#[test]
+fn test_consistent_lock() {
+    let result = validate(
+        r#"
+[[package]]
+name = "requests"
+version = "2.31.0"
+
+[package.dependencies]
+idna = ">=2.5,<4"
+PySocks = { version = ">=1.5.6,!=1.5.7", optional = true }
+urllib3 = [
+    { version = ">=1.21.1,<2", markers = "python_version < \"3.8\"" },
+    { version = ">=1.21.1,<3", markers = "python_version >= \"3.8\"" },
+]
+
+[[package]]
+name = "idna"
+version = "3.6"
+
+[[package]]
+name = "urllib3"
+version = "2.1.0"
+"#,
+    );
+
+    assert!(result.is_valid());
+    assert_eq!(result.problem_count(), 0);
+}
+
+#[test]
+fn test_reports_missing_and_unsatisfied_requirements() {
+    let result = validate(
+        r#"
+[[package]]
+name = "requests"
+version = "2.31.0"
+
+[package.dependencies]
+certifi = ">=2017.4.17"
+charset-normalizer = ">=2,<4"
+idna = ">=2.5,<4"
+urllib3 = ">=1.21.1,<3"
+
+[[package]]
+name = "charset_normalizer"
+version = "3.3.2"
+
+[[package]]
+name = "idna"
+version = "4.0"
+
+[[package]]
+name = "urllib3"
+version = "not-a-version"
+"#,
+    );
+
+    assert!(!result.is_valid());
+    assert_eq!(result.missing.len(), 1);
+    assert_eq!(result.missing[0].package_name, "requests");
+    assert_eq!(result.missing[0].dependency_name, "certifi");
+    assert!(result.missing[0].locked_version.is_none());
+
+    assert_eq!(result.unsatisfied.len(), 1);
+    assert_eq!(result.unsatisfied[0].dependency_name, "idna");
+    assert_eq!(result.unsatisfied[0].constraint, ">=2.5,<4");
+    assert_eq!(result.unsatisfied[0].locked_version.as_deref(), Some("4.0"));
+}
+
+#[test]
+fn test_reports_unsatisfied_marker_alternatives() {
+    let result = validate(
+        r#"
+[[package]]
+name = "pandas"
+version = "2.2.0"
+
+[package.dependencies]
+numpy = [
+    { version = ">=1.22.4,<2", markers = "python_version < \"3.11\"" },
+    { version = ">=1.23.2,<2", markers = "python_version == \"3.11\"" },
+]
+
+[[package]]
+name = "numpy"
+version = "2.0.0"
+"#,
+    );
+
+    assert_eq!(result.unsatisfied.len(), 1);
+    assert_eq!(
+        result.unsatisfied[0].constraint,
+        ">=1.22.4,<2 || >=1.23.2,<2"
+    );
+}
+
+#[test]
+fn test_skips_marker_guarded_requirements_that_are_not_locked() {
+    let result = validate(
+        r#"
+[[package]]
+name = "black"
+version = "24.1.0"
+
+[package.dependencies]
+click = ">=8.0.0"
+tomli = { version = ">=1.1.0", markers = "python_version < \"3.11\"" }
+
+[[package]]
+name = "click"
+version = "8.1.7"
+
+[metadata]
+lock-version = "2.0"
+python-versions = "^3.11"
+"#,
+    );
+
+    assert!(result.is_valid());
+    assert!(result.missing.is_empty());
+}
+
+#[test]
+fn test_reports_cycles_without_failing() {
+    let result = validate(
+        r#"
+[[package]]
+name = "sphinx"
+version = "7.2.6"
+
+[package.dependencies]
+sphinxcontrib-serializinghtml = ">=1.1.9"
+
+[[package]]
+name = "sphinxcontrib-serializinghtml"
+version = "1.1.10"
+
+[package.dependencies]
+Sphinx = ">=5"
+"#,
+    );
+
+    assert!(result.is_valid());
+    assert_eq!(result.problem_count(), 0);
+    assert_eq!(
+        result.cycles,
+        vec![vec![
+            "sphinx".to_string(),
+            "sphinxcontrib-serializinghtml".to_string(),
+            "sphinx".to_string()
+        ]]
+    );
+}